@@ -0,0 +1,210 @@
+//! Functions for analysing chords and the relationships between them.
+
+use std::collections::BTreeMap;
+
+use chord::*;
+use interval::Interval;
+
+/// Returns the lattice coordinates of a pitch class on the Tonnetz.
+///
+/// The horizontal axis moves by perfect fifths and the vertical axis by major
+/// thirds. Since the Tonnetz wraps around, the coordinates are reduced into
+/// the region `-1 <= x < 3`, `-1 <= y < 2` which places `C` at the origin.
+fn lattice_position(pitch_class: u8) -> (i32, i32) {
+    for x in -1i32..3 {
+        for y in -1..2 {
+            if (7 * x + 4 * y).rem_euclid(12) == i32::from(pitch_class) {
+                return (x, y);
+            }
+        }
+    }
+
+    unreachable!()
+}
+
+/// Returns the position of a major or minor triad on the Tonnetz.
+///
+/// Each triad is a triangle on the lattice of pitch classes. Triangles within
+/// the same row are numbered from left to right, alternating between major
+/// and minor, so a major triad at `(x, y)` has its parallel at `(x + 1, y - 1)`,
+/// its leading-tone exchange at `(x + 1, y)` and its relative at `(x - 1, y)`.
+/// `C` major is placed at the origin.
+///
+/// Any slash root is ignored. Returns `None` if the chord is not a major or
+/// minor triad.
+pub fn tonnetz_position(chord: &Chord) -> Option<(i32, i32)> {
+    let (x, y) = lattice_position(chord.root.pitch_class());
+
+    if chord.structure == ChordStructure::major_triad() {
+        Some((2 * x, y))
+    } else if chord.structure == ChordStructure::minor_triad() {
+        // The minor triangle sits in the row below its root, sharing its
+        // upper edge with the parallel major.
+        Some((2 * x + 1, y - 1))
+    } else {
+        None
+    }
+}
+
+/// Returns the guide tones of a chord, its third and seventh, where present.
+fn guide_tones(chord: &Chord) -> Vec<Note> {
+    [PitchClass::N3, PitchClass::N7].iter()
+        .filter_map(|&class| {
+            chord.structure.get(class)
+                .map(|offset| chord.root.get_relative((class, offset)))
+        })
+        .collect()
+}
+
+/// Returns the guide tone line moving from chord `a` to chord `b`.
+///
+/// Each guide tone (the third and seventh) of `a` is paired with whichever
+/// guide tone of `b` lies the fewest semi-tones away in either direction,
+/// favouring the third of `b` if both are equally near. For a `Dm7` to `G7`
+/// the seventh `C` resolves down to the third `B`, while the third `F` is held
+/// as the seventh of `G7`.
+///
+/// Guide tones missing from either chord are skipped, so the line is empty if
+/// `b` has neither a third nor a seventh.
+pub fn guide_tone_line(a: &Chord, b: &Chord) -> Vec<(Note, Note)> {
+    let targets = guide_tones(b);
+    let distance = |from: &Note, to: &Note| {
+        let up = (12 + to.pitch_class() - from.pitch_class()) % 12;
+        up.min(12 - up)
+    };
+
+    guide_tones(a).into_iter()
+        .filter_map(|tone| {
+            targets.iter()
+                .min_by_key(|target| distance(&tone, target))
+                .map(|&target| (tone, target))
+        })
+        .collect()
+}
+
+/// Returns the interval the root moves by from chord `a` up to chord `b`.
+///
+/// The interval is ascending, so its inversion gives the equivalent motion
+/// downwards. For example, `G7` to `C` rises by a perfect fourth, or falls by
+/// a perfect fifth. Any slash roots are ignored.
+pub fn root_motion(a: &Chord, b: &Chord) -> Interval {
+    Interval::between(&a.root, &b.root)
+}
+
+/// Returns whether chords `a` and `b` are tritone substitutes for one
+/// another.
+///
+/// This is the case when both are dominant sevenths (see
+/// `Chord::is_dominant_functioning`) with roots a tritone apart, and so share
+/// their guide tones with the third and seventh swapped. For example, `G7`
+/// and `Db7` share `B` (or `Cb`) and `F`.
+pub fn is_tritone_sub(a: &Chord, b: &Chord) -> bool {
+    let pitch_classes = |chord: &Chord| {
+        let mut classes: Vec<_> = guide_tones(chord).iter().map(Note::pitch_class).collect();
+        classes.sort();
+        classes
+    };
+
+    let interval = (12 + b.root.pitch_class() - a.root.pitch_class()) % 12;
+
+    a.is_dominant_functioning() && b.is_dominant_functioning()
+        && interval == 6
+        && pitch_classes(a) == pitch_classes(b)
+}
+
+/// Returns the number of notes of a voicing, such as that returned by
+/// `Chord::voicing`, which lie in each octave.
+///
+/// Octaves without any notes are omitted. Many notes within a low octave
+/// make for a muddy sounding voicing.
+pub fn notes_per_octave(voicing: &[(Note, i8)]) -> BTreeMap<i8, usize> {
+    let mut counts = BTreeMap::new();
+
+    for &(_, octave) in voicing {
+        *counts.entry(octave).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(symbol: &str) -> Option<(i32, i32)> {
+        tonnetz_position(&Chord::from_shorthand(symbol).unwrap())
+    }
+
+    #[test]
+    fn tonnetz_neighbours() {
+        assert_eq!(position("C"), Some((0, 0)));
+
+        // Parallel, leading-tone exchange and relative
+        assert_eq!(position("Cm"), Some((1, -1)));
+        assert_eq!(position("Em"), Some((1, 0)));
+        assert_eq!(position("Am"), Some((-1, 0)));
+
+        // Dominant and subdominant lie a fifth either side
+        assert_eq!(position("G"), Some((2, 0)));
+        assert_eq!(position("F"), Some((-2, 0)));
+    }
+
+    #[test]
+    fn tonnetz_enharmonic() {
+        assert_eq!(position("Db"), position("C#"));
+    }
+
+    #[test]
+    fn tonnetz_non_triad() {
+        assert_eq!(position("C7"), None);
+        assert_eq!(position("Cdim"), None);
+    }
+
+    #[test]
+    fn guide_tone_line_ii_v() {
+        use chord::NoteClass::*;
+
+        let dm7 = Chord::from_shorthand("Dm7").unwrap();
+        let g7 = Chord::from_shorthand("G7").unwrap();
+
+        let line = vec![
+            (Note::new(F, 0), Note::new(F, 0)),
+            (Note::new(C, 0), Note::new(B, 0)),
+        ];
+
+        assert_eq!(guide_tone_line(&dm7, &g7), line);
+    }
+
+    #[test]
+    fn root_motion_dominant_to_tonic() {
+        use interval::IntervalQuality::Perfect;
+
+        let g7 = Chord::from_shorthand("G7").unwrap();
+        let c = Chord::from_shorthand("C").unwrap();
+
+        assert_eq!(root_motion(&g7, &c), Interval::new(Perfect, 4).unwrap());
+        assert_eq!(root_motion(&g7, &c).invert(), Interval::new(Perfect, 5).unwrap());
+        assert_eq!(root_motion(&c, &g7), Interval::new(Perfect, 5).unwrap());
+    }
+
+    #[test]
+    fn tritone_substitutes() {
+        let chord = |symbol| Chord::from_shorthand(symbol).unwrap();
+
+        assert!(is_tritone_sub(&chord("G7"), &chord("Db7")));
+        assert!(is_tritone_sub(&chord("Db7"), &chord("G7")));
+        assert!(is_tritone_sub(&chord("G9"), &chord("C#7")));
+        assert!(!is_tritone_sub(&chord("G7"), &chord("Db")));
+        assert!(!is_tritone_sub(&chord("G7"), &chord("D7")));
+        assert!(!is_tritone_sub(&chord("GMaj7"), &chord("DbMaj7")));
+    }
+
+    #[test]
+    fn notes_per_octave_spread() {
+        let voicing = Chord::from_shorthand("C13").unwrap().voicing(3);
+        let counts: Vec<_> = notes_per_octave(&voicing).into_iter().collect();
+
+        assert_eq!(counts, vec![(3, 4), (4, 3)]);
+        assert!(notes_per_octave(&[]).is_empty());
+    }
+}
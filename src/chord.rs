@@ -23,15 +23,27 @@
 //!
 //! // Shorthand construction
 //! let chord2 = Chord::from_shorthand("A#Maj13(#5,#11)").unwrap();
+//!
+//! assert_eq!(chord1, chord2);
+//! assert_eq!(chord1.to_string(), "A#Maj13(#5,#11)");
 //! ```
 //!
 //! All `Chord`'s have an implicit root pitch class.
 
+use std::convert::TryFrom;
 use std::fmt::{self, Write};
-use std::iter;
 
+#[cfg(feature = "arbitrary")]
+use arbitrary::{self, Arbitrary, Unstructured};
 use combine::Parser;
+use combine::primitives::Consumed;
+#[cfg(feature = "serde")]
+use json;
+use midi;
 use parser::{parse_chord, parse_polychord};
+#[cfg(feature = "serde")]
+use serde_json;
+use scale::{Mode, Scale};
 
 /// Represents an error which may occur when parsing shorthand chord forms.
 pub use combine::ParseError;
@@ -146,7 +158,7 @@ impl PitchClass {
     }
 
     /// Allows `PitchClass` to be used as an indexable element.
-    pub fn index(&self) -> usize {
+    pub const fn index(&self) -> usize {
         use self::PitchClass::*;
 
         match *self {
@@ -180,6 +192,9 @@ impl PitchClass {
     }
 
     /// Returns the number of semi-tones difference this `PitchClass` represents.
+    ///
+    /// As in chord symbols, an unaltered seventh is a minor seventh above the
+    /// root. A major seventh is represented as a sharpened `PitchClass::N7`.
     pub fn to_relative_difference(&self) -> usize {
         use self::PitchClass::*;
 
@@ -190,7 +205,7 @@ impl PitchClass {
             N4  => 5,
             N5  => 7,
             N6  => 9,
-            N7  => 11,
+            N7  => 10,
             N9  => 14,
             N11 => 17,
             N13 => 21,
@@ -218,6 +233,45 @@ impl PitchClass {
             _   => &[]
         }
     }
+
+    /// Construct a pitch-class from its interval number, as returned by
+    /// `PitchClass::number`.
+    pub fn from_number(number: u8) -> Option<Self> {
+        use self::PitchClass::*;
+
+        match number {
+            1  => Some(N1),
+            2  => Some(N2),
+            3  => Some(N3),
+            4  => Some(N4),
+            5  => Some(N5),
+            6  => Some(N6),
+            7  => Some(N7),
+            9  => Some(N9),
+            11 => Some(N11),
+            13 => Some(N13),
+            _  => None,
+        }
+    }
+
+    /// Returns the interval number of this `PitchClass` as written in a chord
+    /// symbol, such as `9` for `PitchClass::N9`.
+    pub fn number(&self) -> u8 {
+        use self::PitchClass::*;
+
+        match *self {
+            N1  => 1,
+            N2  => 2,
+            N3  => 3,
+            N4  => 4,
+            N5  => 5,
+            N6  => 6,
+            N7  => 7,
+            N9  => 9,
+            N11 => 11,
+            N13 => 13,
+        }
+    }
 }
 
 /// Represents an alteration of a base `NoteClass`.
@@ -226,6 +280,27 @@ impl PitchClass {
 /// repeated sharps and negative values representing repeated flats.
 pub type PitchOffset = i8;
 
+/// Which accidental to use when spelling a pitch class which has no natural
+/// note.
+///
+/// `Auto` chooses the commonly used spelling of each black key, being `C#`,
+/// `Eb`, `F#`, `Ab` and `Bb`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SpellingPreference {
+    Sharps, Flats, Auto
+}
+
+impl SpellingPreference {
+    /// Returns the accidental direction passed to `Note::spelled`.
+    fn hint(&self) -> PitchOffset {
+        match *self {
+            SpellingPreference::Sharps => 1,
+            SpellingPreference::Flats => -1,
+            SpellingPreference::Auto => 0,
+        }
+    }
+}
+
 /// A single note which may have applied accidentals.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Note {
@@ -242,11 +317,174 @@ impl Note {
         Note { root, offset }
     }
 
+    /// Returns the number of accidentals this note is spelled with.
+    pub fn accidental_count(&self) -> u8 {
+        self.offset.unsigned_abs()
+    }
+
+    /// Returns the chromatic pitch class of this `Note`, with `C` as `0`.
+    ///
+    /// Enharmonic notes such as `C#` and `Db` share the same pitch class.
+    pub fn pitch_class(&self) -> u8 {
+        let natural = NoteClass::C.difference(&self.root) as i32;
+        (natural + i32::from(self.offset)).rem_euclid(12) as u8
+    }
+
+    /// Returns whether this note is played on a black key of a piano.
+    ///
+    /// This depends only on the pitch class, so `C#` is black while `Cb` and
+    /// `B#`, which sound as `B` and `C`, are white.
+    pub fn is_black_key(&self) -> bool {
+        matches!(self.pitch_class(), 1 | 3 | 6 | 8 | 10)
+    }
+
+    /// Returns the absolute chromatic pitch of this note within the given
+    /// octave, using scientific pitch notation octave numbering.
+    ///
+    /// The value is `octave * 12` plus the semi-tones above `C`, so `C4` is
+    /// `48`. Accidentals are not wrapped, so `B#4` and `C5` are equal.
+    pub fn absolute_pitch(&self, octave: i8) -> i32 {
+        let natural = NoteClass::C.difference(&self.root) as i32;
+        i32::from(octave) * 12 + natural + i32::from(self.offset)
+    }
+
+    /// Returns the MIDI note number of this note within the given octave.
+    ///
+    /// Middle C (`C4`) is MIDI note `60`. Returns `None` if the note lies
+    /// outside of the MIDI range.
+    pub fn to_midi(&self, octave: i8) -> Option<u8> {
+        let midi = self.absolute_pitch(octave) + 12;
+
+        if (0..=127).contains(&midi) {
+            Some(midi as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the frequency in hertz of this note within the given octave
+    /// using twelve-tone equal temperament tuned to `a4_hz`.
+    pub fn frequency(&self, octave: i8, a4_hz: f64) -> f64 {
+        let a4 = Note::new(NoteClass::A, 0).absolute_pitch(4);
+        let semitones = self.absolute_pitch(octave) - a4;
+        a4_hz * (f64::from(semitones) / 12.0).exp2()
+    }
+
+    /// Returns the octave this note lies in when sounding at the given
+    /// absolute pitch.
+    fn octave_at(&self, absolute_pitch: i32) -> i8 {
+        (absolute_pitch - self.absolute_pitch(0)).div_euclid(12) as i8
+    }
+
+    /// Returns every spelling of this note's pitch class which uses at most a
+    /// double accidental, including this note if it qualifies.
+    ///
+    /// Spellings are ordered by their base `NoteClass`, starting from `C`.
+    pub fn enharmonics(&self) -> Vec<Note> {
+        use self::NoteClass::*;
+
+        let pitch_class = i32::from(self.pitch_class());
+
+        [C, D, E, F, G, A, B].iter()
+            .filter_map(|&root| {
+                let natural = NoteClass::C.difference(&root) as i32;
+                let offset = (pitch_class - natural + 6).rem_euclid(12) - 6;
+
+                if offset.abs() <= 2 {
+                    Some(Note::new(root, offset as PitchOffset))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Construct the `Note` with the given pitch class, with `C` as `0`.
+    ///
+    /// The note is spelled as a natural where possible and otherwise with a
+    /// single accidental as given by `preference`. The pitch class is reduced
+    /// to within an octave.
+    pub fn from_pitch_class(pitch_class: u8, preference: SpellingPreference) -> Note {
+        Note::spelled(pitch_class % 12, preference.hint())
+    }
+
+    /// Respell this note with as few accidentals as possible, as per
+    /// `Note::from_pitch_class`.
+    ///
+    /// For example, `Fb` becomes `E` and `G##` becomes `A`.
+    pub fn simplify(&self, preference: SpellingPreference) -> Note {
+        Note::from_pitch_class(self.pitch_class(), preference)
+    }
+
+    /// Returns the signed number of letter names from this note up to `other`,
+    /// ignoring accidentals.
+    ///
+    /// Letters are ordered from `C` to `B` within an octave, so `C` to `E` is
+    /// `2` and `E` to `C` is `-2`. Adding one gives the number of the interval
+    /// between the notes, such as a third.
+    pub fn letter_distance(&self, other: &Note) -> i32 {
+        // `NoteClass` indices begin from `A`, so shift them to begin from `C`.
+        let letter = |note: &Note| {
+            (note.root.to_int() + NOTE_CLASS_COUNT - 2) % NOTE_CLASS_COUNT
+        };
+
+        letter(other) as i32 - letter(self) as i32
+    }
+
+    /// Return the `Note` the given number of semi-tones away from this one.
+    ///
+    /// The result is spelled as a natural where possible. Otherwise the
+    /// accidental follows that of this note, so flat notes transpose to flat
+    /// notes and sharp notes to sharp notes.
+    pub fn transpose(&self, semitones: i32) -> Note {
+        let pitch_class = (i32::from(self.pitch_class()) + semitones).rem_euclid(12) as u8;
+        Note::spelled(pitch_class, self.offset)
+    }
+
+    /// Return the `Note` the given number of semi-tones away from this one,
+    /// spelled as per `Note::from_pitch_class` regardless of the spelling of
+    /// this note.
+    pub fn transpose_with(&self, semitones: i32, preference: SpellingPreference) -> Note {
+        let pitch_class = (i32::from(self.pitch_class()) + semitones).rem_euclid(12) as u8;
+        Note::from_pitch_class(pitch_class, preference)
+    }
+
+    /// Spell a pitch class as a natural if possible and otherwise with a
+    /// single accidental in the direction of `hint`.
+    ///
+    /// If `hint` is zero the commonly used spelling for the black key is
+    /// chosen (`C#`, `Eb`, `F#`, `Ab`, `Bb`).
+    fn spelled(pitch_class: u8, hint: PitchOffset) -> Note {
+        use self::NoteClass::*;
+
+        const NATURALS: [Option<NoteClass>; 12] = [
+            Some(C), None, Some(D), None, Some(E), Some(F),
+            None, Some(G), None, Some(A), None, Some(B),
+        ];
+
+        const COMMON: [PitchOffset; 12] = [
+            0, 1, 0, -1, 0, 0, 1, 0, -1, 0, -1, 0,
+        ];
+
+        if let Some(root) = NATURALS[pitch_class as usize] {
+            return Note::new(root, 0);
+        }
+
+        let offset = match hint {
+            0 => COMMON[pitch_class as usize],
+            h if h > 0 => 1,
+            _ => -1,
+        };
+
+        let natural = (i32::from(pitch_class) - i32::from(offset)).rem_euclid(12);
+        Note::new(NATURALS[natural as usize].unwrap(), offset)
+    }
+
     /// Return the relative `Note` based on the given pitch-class.
     pub fn get_relative(&self, (class, offset): ChordComponent) -> Note {
         let root_val = (self.root.to_int() + class.to_int()) % NOTE_CLASS_COUNT;
         let root_note = NoteClass::from_int(root_val).unwrap();
-        let rel_offset = class.to_relative_difference() as i8
+        let rel_offset = (class.to_relative_difference() % 12) as i8
                          - self.root.difference(&root_note) as i8;
 
         Note {
@@ -268,12 +506,74 @@ impl fmt::Display for Note {
     }
 }
 
+/// A note which may be detuned by a number of cents, for microtonal music.
+///
+/// There are `100` cents in a semi-tone, so a quarter-tone sharp is `50`
+/// cents above the spelled note.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MicroNote {
+    /// The spelled note
+    pub note: Note,
+
+    /// The detuning from the spelled note, in cents
+    pub cents: i16,
+}
+
+impl MicroNote {
+    /// Construct and return a new `MicroNote`.
+    pub fn new(note: Note, cents: i16) -> MicroNote {
+        MicroNote { note, cents }
+    }
+
+    /// Returns the frequency in hertz of this note within the given octave
+    /// as per `Note::frequency`, adjusted by its detuning.
+    pub fn frequency(&self, octave: i8, a4_hz: f64) -> f64 {
+        self.note.frequency(octave, a4_hz) * (f64::from(self.cents) / 1200.0).exp2()
+    }
+}
+
+impl From<Note> for MicroNote {
+    fn from(note: Note) -> MicroNote {
+        MicroNote::new(note, 0)
+    }
+}
+
 /// A relative note within a chord by its intervallic representation.
 ///
 /// For example, a (`PitchClass::n7`, 1) would represent a sharpened seventh,
 /// as found in a major seventh chord.
 pub type ChordComponent = (PitchClass, PitchOffset);
 
+/// The quality of a tertian chord.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ChordQuality {
+    Major, Minor, Dominant, Diminished, HalfDiminished, Augmented
+}
+
+/// How far a tertian chord is extended beyond its triad.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ChordExtent {
+    Triad, Seventh, Ninth, Eleventh, Thirteenth
+}
+
+impl ChordExtent {
+    /// Returns the highest interval present in a chord of this extent.
+    pub fn to_pitch_class(&self) -> PitchClass {
+        use self::ChordExtent::*;
+
+        match *self {
+            Triad      => PitchClass::N5,
+            Seventh    => PitchClass::N7,
+            Ninth      => PitchClass::N9,
+            Eleventh   => PitchClass::N11,
+            Thirteenth => PitchClass::N13,
+        }
+    }
+}
+
+/// The number of bytes in the binary encoding of a `ChordStructure`.
+pub const STRUCTURE_BYTES: usize = 2 + PITCH_CLASS_COUNT;
+
 /// Represents the intervallic structure of a chord.
 ///
 /// This is relative to a root note so a transposition is very cheap.
@@ -284,13 +584,55 @@ impl ChordStructure {
     /// Construct and return a new `ChordStructure`.
     ///
     /// This will always have the root note (`PitchClass::n1`) by default.
-    pub fn new() -> ChordStructure {
+    pub const fn new() -> ChordStructure {
         let mut classes = [None; PITCH_CLASS_COUNT];
         classes[PitchClass::N1.index()] = Some(0);
 
         ChordStructure(classes)
     }
 
+    /// Construct the `ChordStructure` of a major triad.
+    pub fn major_triad() -> ChordStructure {
+        ChordStructure::new()
+            .insert_many(&[(PitchClass::N3, 0), (PitchClass::N5, 0)])
+    }
+
+    /// Construct the `ChordStructure` of a minor triad.
+    pub fn minor_triad() -> ChordStructure {
+        ChordStructure::new()
+            .insert_many(&[(PitchClass::N3, -1), (PitchClass::N5, 0)])
+    }
+
+    /// Construct the `ChordStructure` of a chord with the given quality,
+    /// extended up to `extent`.
+    ///
+    /// Only the seventh is affected by the quality beyond the triad, with all
+    /// further extensions being unaltered. `Dominant` and `HalfDiminished`
+    /// triads are the same as their `Major` and `Diminished` counterparts.
+    pub fn from_quality(quality: ChordQuality, extent: ChordExtent) -> ChordStructure {
+        use self::ChordQuality::*;
+
+        let (third, fifth, seventh) = match quality {
+            Major          => (0, 0, 1),
+            Minor          => (-1, 0, 0),
+            Dominant       => (0, 0, 0),
+            Diminished     => (-1, -1, -1),
+            HalfDiminished => (-1, -1, 0),
+            Augmented      => (0, 1, 0),
+        };
+
+        let structure = ChordStructure::new()
+            .insert_many(&[(PitchClass::N3, third), (PitchClass::N5, fifth)]);
+
+        if extent == ChordExtent::Triad {
+            structure
+        } else {
+            structure
+                .insert_many(extent.to_pitch_class().extended_intervals())
+                .insert((PitchClass::N7, seventh))
+        }
+    }
+
     /// Construct a `ChordStructure` from a single component.
     ///
     /// This is provided to simplify merging alterations into a core chord and
@@ -306,7 +648,7 @@ impl ChordStructure {
     ///
     /// This will overwrite any existing component if the interval was already
     /// set.
-    pub fn insert(mut self, component: ChordComponent) -> ChordStructure {
+    pub const fn insert(mut self, component: ChordComponent) -> ChordStructure {
         self.0[component.0.index()] = Some(component.1);
         self
     }
@@ -322,6 +664,175 @@ impl ChordStructure {
         self
     }
 
+    /// Remove an interval from this `ChordStructure` if it is present.
+    pub fn remove(mut self, class: PitchClass) -> ChordStructure {
+        self.0[class.index()] = None;
+        self
+    }
+
+    /// Construct the `ChordStructure` formed by the given notes above `root`.
+    ///
+    /// The interval of each note is determined from its spelling, so `D#` is
+    /// a sharpened ninth above `C` while `Eb` is a minor third. Seconds are
+    /// taken as ninths, while fourths and sixths are taken as elevenths and
+    /// thirteenths only if a seventh is present. Notes spelled with the same
+    /// letter as the root are taken as the root.
+    ///
+    /// Returns `None` if two notes compete for the same interval, such as
+    /// both `G` and `G#` above `C`.
+    pub fn from_notes(root: &Note, notes: &[Note]) -> Option<ChordStructure> {
+        use self::PitchClass::*;
+
+        const NATURAL_OFFSETS: [i32; NOTE_CLASS_COUNT] = [0, 2, 4, 5, 7, 9, 10];
+
+        let steps = |note: &Note| (note.root.to_int() + NOTE_CLASS_COUNT
+                                   - root.root.to_int()) % NOTE_CLASS_COUNT;
+        let has_seventh = notes.iter().any(|note| steps(note) == 6);
+
+        let mut classes = [None; PITCH_CLASS_COUNT];
+
+        for note in notes {
+            let step = steps(note);
+            let class = match step {
+                0 => N1,
+                1 => N9,
+                2 => N3,
+                3 => if has_seventh { N11 } else { N4 },
+                4 => N5,
+                5 => if has_seventh { N13 } else { N6 },
+                6 => N7,
+                _ => unreachable!(),
+            };
+
+            let semitones = i32::from(note.pitch_class()) - i32::from(root.pitch_class());
+            let offset = (semitones - NATURAL_OFFSETS[step] + 6).rem_euclid(12) - 6;
+            let offset = Some(offset as PitchOffset);
+
+            match classes[class.index()] {
+                None => classes[class.index()] = offset,
+                Some(_) if classes[class.index()] == offset => (),
+                Some(_) => return None,
+            }
+        }
+
+        Some(ChordStructure(classes))
+    }
+
+    /// Encode this `ChordStructure` into a compact binary form.
+    ///
+    /// The first two bytes are a little-endian bitmask with bit `n` set if the
+    /// interval with index `n` (see `PitchClass::index`) is present. The
+    /// remaining bytes hold the offset of each interval in index order as a
+    /// two's complement value, which is zero for absent intervals.
+    pub fn to_bytes(&self) -> [u8; STRUCTURE_BYTES] {
+        let mut bytes = [0; STRUCTURE_BYTES];
+        let mut present: u16 = 0;
+
+        for (i, class) in self.0.iter().enumerate() {
+            if let Some(offset) = *class {
+                present |= 1 << i;
+                bytes[2 + i] = offset as u8;
+            }
+        }
+
+        bytes[0] = present as u8;
+        bytes[1] = (present >> 8) as u8;
+        bytes
+    }
+
+    /// Decode a `ChordStructure` from the form produced by `to_bytes`.
+    ///
+    /// Unused bits of the bitmask and the offsets of absent intervals are
+    /// ignored.
+    pub fn from_bytes(bytes: [u8; STRUCTURE_BYTES]) -> ChordStructure {
+        let present = u16::from(bytes[0]) | u16::from(bytes[1]) << 8;
+        let mut classes = [None; PITCH_CLASS_COUNT];
+
+        for (i, class) in classes.iter_mut().enumerate() {
+            if present & (1 << i) != 0 {
+                *class = Some(bytes[2 + i] as PitchOffset);
+            }
+        }
+
+        ChordStructure(classes)
+    }
+
+    /// Check that this structure describes a sensible chord.
+    ///
+    /// The root must be present and unaltered, and an interval may not appear
+    /// in both its simple and compound forms (such as the `2` and `9`) with
+    /// different offsets, as both would then be spelled with the same letter.
+    /// The structure of a rootless voicing, as from `Chord::rootless`, is
+    /// reported as `StructureError::MissingRoot` like any other without a
+    /// root.
+    pub fn validate(&self) -> Result<(), StructureError> {
+        use self::PitchClass::*;
+
+        match self.get(N1) {
+            None => return Err(StructureError::MissingRoot),
+            Some(0) => (),
+            Some(_) => return Err(StructureError::AlteredRoot),
+        }
+
+        for &(simple, compound) in &[(N2, N9), (N4, N11), (N6, N13)] {
+            if let (Some(a), Some(b)) = (self.get(simple), self.get(compound)) {
+                if a != b {
+                    return Err(StructureError::ConflictingInterval(simple, compound));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the quality of the tertian chord this structure forms.
+    ///
+    /// The quality is determined by the third, fifth and seventh, with a
+    /// missing fifth taken as perfect. A minor triad with a major seventh is
+    /// `Minor`, while an augmented triad is `Augmented` whatever its seventh.
+    /// Returns `None` for structures without a third, such as power chords,
+    /// and those whose intervals fit no quality.
+    pub fn quality(&self) -> Option<ChordQuality> {
+        use self::ChordQuality::*;
+
+        let fifth = self.get(PitchClass::N5).unwrap_or(0);
+
+        match (self.get(PitchClass::N3)?, fifth, self.get(PitchClass::N7)) {
+            (0, 0, None) | (0, 0, Some(1)) => Some(Major),
+            (0, 0, Some(0)) => Some(Dominant),
+            (-1, 0, None) | (-1, 0, Some(0)) | (-1, 0, Some(1)) => Some(Minor),
+            (-1, -1, None) | (-1, -1, Some(-1)) => Some(Diminished),
+            (-1, -1, Some(0)) => Some(HalfDiminished),
+            (0, 1, _) => Some(Augmented),
+            _ => None,
+        }
+    }
+
+    /// Returns the offset of the given interval if it is present.
+    pub fn get(&self, class: PitchClass) -> Option<PitchOffset> {
+        self.0[class.index()]
+    }
+
+    /// Split this structure into its triad and its extensions.
+    ///
+    /// The triad holds the root, third and fifth that are present, and the
+    /// extensions hold every other interval. Unlike structures built with
+    /// `ChordStructure::new` the extensions have no root, so merging them
+    /// back onto the triad gives this structure again.
+    pub fn split_extensions(&self) -> (ChordStructure, ChordStructure) {
+        let mut triad = ChordStructure([None; PITCH_CLASS_COUNT]);
+        let mut extensions = ChordStructure([None; PITCH_CLASS_COUNT]);
+
+        for i in 0..PITCH_CLASS_COUNT {
+            match PitchClass::from_int(i).unwrap() {
+                PitchClass::N1 | PitchClass::N3 | PitchClass::N5 => triad.0[i] = self.0[i],
+                _ => extensions.0[i] = self.0[i],
+            }
+        }
+
+        (triad, extensions)
+    }
+
     /// Merge two `ChordStructure`'s together with preference for elements
     /// within the `other` structure.
     pub fn merge(mut self, other: &ChordStructure) -> ChordStructure {
@@ -334,6 +845,253 @@ impl ChordStructure {
     }
 }
 
+impl fmt::Display for ChordStructure {
+    /// Formats the structure as the suffix of a chord symbol, such as
+    /// `m7(b5)`.
+    ///
+    /// The output is accepted by `Chord::from_shorthand`. A missing root or
+    /// fifth is written as an omission, such as `Maj7(omit1)` for a rootless
+    /// voicing, and a second or fourth in place of the third as a suspension,
+    /// such as `7sus4`. Components which have no shorthand form, such as a
+    /// second added tone, are left out.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::PitchClass::*;
+
+        let (third, fifth, seventh) = (self.get(N3), self.get(N5), self.get(N7));
+        let natural_extensions = [N6, N9, N11, N13].iter()
+            .any(|&class| self.get(class) == Some(0));
+
+        // A natural fourth, or failing that second, without a third is taken
+        // to be a suspension of it.
+        let suspension = [N4, N2].iter()
+            .find(|&&class| third.is_none() && self.get(class) == Some(0));
+
+        let mut implied = ChordStructure::new();
+
+        if third.is_none() && suspension.is_none() {
+            f.write_char('5')?;
+            implied = implied.insert((N5, 0));
+        } else if third == Some(-1) && fifth == Some(-1)
+            && (seventh == Some(-1) || seventh.is_none() && !natural_extensions)
+        {
+            f.write_str("dim")?;
+            implied = implied.insert_many(&[(N3, -1), (N5, -1)]);
+
+            if seventh.is_some() {
+                f.write_char('7')?;
+                implied = implied.insert((N7, -1));
+            }
+        } else if third == Some(0) && fifth == Some(1)
+            && matches!(seventh, None | Some(0)) && !natural_extensions
+        {
+            f.write_str("aug")?;
+            implied = implied.insert_many(&[(N3, 0), (N5, 1)]);
+
+            if seventh.is_some() {
+                f.write_char('7')?;
+                implied = implied.insert((N7, 0));
+            }
+        } else {
+            if third == Some(-1) {
+                f.write_char('m')?;
+            }
+
+            match seventh {
+                None if self.get(N6) == Some(0) => {
+                    f.write_char('6')?;
+                    implied = implied.insert((N6, 0));
+
+                    if self.get(N9) == Some(0) {
+                        f.write_str("/9")?;
+                        implied = implied.insert((N9, 0));
+                    }
+                }
+
+                None => (),
+
+                Some(offset) => {
+                    if offset == 1 {
+                        f.write_str("Maj")?;
+                    }
+
+                    // The named extension implies all those below it, so an
+                    // altered ninth beneath a natural eleventh is written as
+                    // an alteration of the eleventh chord.
+                    let mut extent = N7;
+                    for &class in &[N9, N11, N13] {
+                        match self.get(class) {
+                            Some(0) => extent = class,
+                            Some(_) => (),
+                            None => break,
+                        }
+                    }
+
+                    write!(f, "{}", extent.number())?;
+                    implied = implied
+                        .insert_many(extent.extended_intervals())
+                        .insert((N7, if offset == 1 { 1 } else { 0 }));
+                }
+            }
+
+            implied = match suspension {
+                Some(&class) => {
+                    write!(f, "sus{}", class.number())?;
+                    implied.insert_many(&[(class, 0), (N5, 0)])
+                }
+
+                None => implied
+                    .insert_many(&[(N3, if third == Some(-1) { -1 } else { 0 }), (N5, 0)]),
+            };
+        }
+
+        let alterations: Vec<String> = [N5, N6, N9, N11, N13].iter()
+            .filter_map(|&class| {
+                let offset = self.get(class)?;
+                let accidental = match offset {
+                    -1 => 'b',
+                    1  => '#',
+                    2  => '𝄪',
+                    _  => return None,
+                };
+
+                if implied.get(class) == Some(offset) {
+                    None
+                } else {
+                    Some(format!("{}{}", accidental, class.number()))
+                }
+            })
+            .collect();
+
+        if !alterations.is_empty() {
+            write!(f, "({})", alterations.join(","))?;
+        }
+
+        let omissions: Vec<String> = [N1, N3, N5].iter()
+            .filter(|&&class| implied.get(class).is_some() && self.get(class).is_none())
+            .map(|class| class.number().to_string())
+            .collect();
+
+        if !omissions.is_empty() {
+            write!(f, "(omit{})", omissions.join(","))?;
+        }
+
+        let addition = [N2, N4, N6, N9, N11, N13].iter()
+            .find(|&&class| self.get(class) == Some(0) && implied.get(class) != Some(0));
+
+        if let Some(class) = addition {
+            write!(f, "/{}", class.number())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The known chord qualities, each given by its shorthand suffix (as written
+/// by `Display`) along with its structure.
+///
+/// These are the structures recognized when constructing a `Chord` from its
+/// notes.
+pub const TEMPLATES: &[(&str, ChordStructure)] = {
+    use self::PitchClass::*;
+
+    const MAJOR: ChordStructure = ChordStructure::new().insert((N3, 0)).insert((N5, 0));
+    const MINOR: ChordStructure = ChordStructure::new().insert((N3, -1)).insert((N5, 0));
+    const DIMINISHED: ChordStructure = ChordStructure::new().insert((N3, -1)).insert((N5, -1));
+    const AUGMENTED: ChordStructure = ChordStructure::new().insert((N3, 0)).insert((N5, 1));
+
+    &[
+        ("", MAJOR),
+        ("m", MINOR),
+        ("dim", DIMINISHED),
+        ("aug", AUGMENTED),
+        ("5", ChordStructure::new().insert((N5, 0))),
+        ("6", MAJOR.insert((N6, 0))),
+        ("m6", MINOR.insert((N6, 0))),
+        ("6/9", MAJOR.insert((N6, 0)).insert((N9, 0))),
+        ("/9", MAJOR.insert((N9, 0))),
+        ("7", MAJOR.insert((N7, 0))),
+        ("Maj7", MAJOR.insert((N7, 1))),
+        ("m7", MINOR.insert((N7, 0))),
+        ("mMaj7", MINOR.insert((N7, 1))),
+        ("m7(b5)", DIMINISHED.insert((N7, 0))),
+        ("dim7", DIMINISHED.insert((N7, -1))),
+        ("aug7", AUGMENTED.insert((N7, 0))),
+        ("9", MAJOR.insert((N7, 0)).insert((N9, 0))),
+        ("Maj9", MAJOR.insert((N7, 1)).insert((N9, 0))),
+        ("m9", MINOR.insert((N7, 0)).insert((N9, 0))),
+        ("11", MAJOR.insert((N7, 0)).insert((N9, 0)).insert((N11, 0))),
+        ("m11", MINOR.insert((N7, 0)).insert((N9, 0)).insert((N11, 0))),
+        ("13", MAJOR.insert((N7, 0)).insert((N9, 0)).insert((N11, 0)).insert((N13, 0))),
+        ("Maj13", MAJOR.insert((N7, 1)).insert((N9, 0)).insert((N11, 0)).insert((N13, 0))),
+        ("m13", MINOR.insert((N7, 0)).insert((N9, 0)).insert((N11, 0)).insert((N13, 0))),
+    ]
+};
+
+/// Returns whether the structure is one of the `TEMPLATES`.
+fn is_recognized(structure: &ChordStructure) -> bool {
+    TEMPLATES.iter().any(|(_, template)| template == structure)
+}
+
+/// Represents an error which may occur when recognizing a chord from notes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RecognitionError {
+    /// Fewer than two notes were given
+    TooFewNotes,
+
+    /// The notes do not form a known chord above any of them
+    NoMatchingTemplate,
+
+    /// The notes form a known chord above more than one of them
+    AmbiguousRoot,
+}
+
+impl fmt::Display for RecognitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::RecognitionError::*;
+
+        let description = match *self {
+            TooFewNotes => "too few notes to form a chord",
+            NoMatchingTemplate => "notes do not form a known chord",
+            AmbiguousRoot => "notes form a known chord above multiple roots",
+        };
+
+        f.write_str(description)
+    }
+}
+
+impl ::std::error::Error for RecognitionError {}
+
+/// Represents a problem with the intervals of a `ChordStructure`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StructureError {
+    /// The root interval is not present
+    MissingRoot,
+
+    /// The root interval is present but altered
+    AlteredRoot,
+
+    /// A simple interval and its compound form are both present with
+    /// different offsets, such as a `2` and a `b9`
+    ConflictingInterval(PitchClass, PitchClass),
+}
+
+impl fmt::Display for StructureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::StructureError::*;
+
+        match *self {
+            MissingRoot => f.write_str("structure has no root"),
+            AlteredRoot => f.write_str("structure has an altered root"),
+            ConflictingInterval(simple, compound) => write!(
+                f, "structure has conflicting intervals {} and {}",
+                simple.number(), compound.number()
+            ),
+        }
+    }
+}
+
+impl ::std::error::Error for StructureError {}
+
 /// A single simple chord comprised of many notes.
 ///
 /// The chord representation used internally is based on tertian harmony.
@@ -362,29 +1120,1081 @@ impl Chord {
         Chord { slash_root: Some(slash_root), root, structure }
     }
 
-    /// Construct a chord from a shorthand string.
-    pub fn from_shorthand(input: &str) -> ParseResult<Chord> {
-        parse_chord().parse(input).map(|c| c.0)
+    /// Construct a chord on the given root with the given quality and extent.
+    ///
+    /// See `ChordStructure::from_quality` for the structures produced.
+    pub fn from_quality(root: Note, quality: ChordQuality, extent: ChordExtent)
+        -> Chord
+    {
+        Chord::new(root, ChordStructure::from_quality(quality, extent))
     }
 
-    /// Return an iterator over each of all notes this chord is comprised of.
+    /// Construct a chord on the given root from the semi-tones each of its
+    /// notes lies above the root.
     ///
-    /// Notes are returned from lowest pitch to highest, in order.
-    pub fn iter(&self) -> NoteIterator {
-        NoteIterator {
-            chord: self,
-            state: NoteIteratorState::Slash,
-        }
-    }
-}
+    /// Each interval within an octave is matched to the nearest pitch class,
+    /// favouring the common chord tones: `3` is a minor third, `6` a flat
+    /// fifth and `8` a sharp fifth. Intervals between one and two octaves are
+    /// matched to the ninth, eleventh or thirteenth where they are one of
+    /// their common alterations, so `15` is a sharp ninth, and are otherwise
+    /// treated as the same interval within the octave. Larger intervals are
+    /// reduced by octaves.
+    ///
+    /// Returns `None` if two intervals are matched to the same pitch class,
+    /// as with a minor and major third.
+    pub fn from_intervals(root: Note, semitones: &[u8]) -> Option<Chord> {
+        use self::PitchClass::*;
 
-/// An iterator over notes in a chord.
-///
-/// Notes are returned lowest to highest in pitch.
-#[derive(Clone, Debug)]
-pub struct NoteIterator<'a> {
-    pub chord: &'a Chord,
-    state: NoteIteratorState,
+        const SIMPLE: [ChordComponent; 12] = [
+            (N1, 0), (N2, -1), (N2, 0), (N3, -1), (N3, 0), (N4, 0),
+            (N5, -1), (N5, 0), (N5, 1), (N6, 0), (N7, 0), (N7, 1),
+        ];
+
+        const COMPOUND: [Option<ChordComponent>; 12] = [
+            None, Some((N9, -1)), Some((N9, 0)), Some((N9, 1)), None, Some((N11, 0)),
+            Some((N11, 1)), None, Some((N13, -1)), Some((N13, 0)), None, None,
+        ];
+
+        let mut structure = ChordStructure::new();
+
+        for &semitone in semitones {
+            let semitone = if semitone >= 24 { 12 + semitone % 12 } else { semitone };
+
+            let simple = usize::from(semitone % 12);
+            let (class, offset) = match COMPOUND[simple] {
+                Some(component) if semitone >= 12 => component,
+                _ => SIMPLE[simple],
+            };
+
+            match structure.get(class) {
+                Some(existing) if existing != offset => return None,
+                _ => structure = structure.insert((class, offset)),
+            }
+        }
+
+        Some(Chord::new(root, structure))
+    }
+
+    /// Construct the power chord on the given root, which has only a root and
+    /// a perfect fifth. This is the `5` chord, such as `E5`.
+    pub fn power_chord(root: Note) -> Chord {
+        Chord::new(root, ChordStructure::new().insert((PitchClass::N5, 0)))
+    }
+
+    /// Recognize a chord from its notes, with the first note being the bass.
+    ///
+    /// If the notes form a known chord (one of the `TEMPLATES`) above the
+    /// bass then that chord is returned. Otherwise, if they form a known chord
+    /// above exactly one of the other notes, a slash chord is returned.
+    /// Intervals are determined from the spelling of each note as per
+    /// `ChordStructure::from_notes`.
+    ///
+    /// A `RecognitionError` is returned if fewer than two notes are given or
+    /// no single chord could be determined.
+    pub fn from_notes(notes: &[Note]) -> Result<Chord, RecognitionError> {
+        if notes.len() < 2 {
+            return Err(RecognitionError::TooFewNotes);
+        }
+
+        let bass = notes[0];
+        let recognize = |root: &Note| {
+            ChordStructure::from_notes(root, notes).filter(is_recognized)
+        };
+
+        if let Some(structure) = recognize(&bass) {
+            return Ok(Chord::new(bass, structure));
+        }
+
+        let mut found = None;
+        for (i, root) in notes.iter().enumerate() {
+            if root.root == bass.root || notes[..i].iter().any(|n| n.root == root.root) {
+                continue;
+            }
+
+            if let Some(structure) = recognize(root) {
+                if found.is_some() {
+                    return Err(RecognitionError::AmbiguousRoot);
+                }
+                found = Some(Chord::new_slash(bass, *root, structure));
+            }
+        }
+
+        found.ok_or(RecognitionError::NoMatchingTemplate)
+    }
+
+    /// Construct a chord from a shorthand string.
+    ///
+    /// A chord holds a single alteration of each interval, so if an interval
+    /// is altered more than once the last alteration wins. For example,
+    /// `C7(b9,#9)` is the same chord as `C7(#9)`, and `C7(b5,#5)` as `Caug7`.
+    pub fn from_shorthand(input: &str) -> ParseResult<'_, Chord> {
+        parse_chord().parse(input).map(|c| c.0)
+    }
+
+    /// Returns the canonical shorthand symbol of this chord.
+    ///
+    /// The many ways of writing the same chord, such as `Cmin7`, `Cm7` and
+    /// `C-7`, are all written in a single form (here `Cm7`). This is the same
+    /// as the `Display` implementation, and the suffixes used are those of
+    /// `TEMPLATES`, such as `m7`, `Maj7` and `m7(b5)`.
+    pub fn canonical_symbol(&self) -> String {
+        self.to_string()
+    }
+
+    /// Returns this chord as a JSON object.
+    ///
+    /// Unlike the shorthand form every chord can be written this way. The
+    /// object has the keys:
+    ///
+    /// * `root`: the `NoteClass` of the root as a string, such as `"C"`
+    /// * `accidental`: the `PitchOffset` of the root
+    /// * `slash_root`: `null`, or an object with its own `root` and
+    ///   `accidental` keys
+    /// * `intervals`: an array of objects with an `interval` number (as per
+    ///   `PitchClass::number`) and its `offset`, in `PitchClass` order
+    ///
+    /// For example, `C/E` is written as:
+    ///
+    /// ```text
+    /// {"root":"C","accidental":0,"slash_root":{"root":"E","accidental":0},
+    ///  "intervals":[{"interval":1,"offset":0},{"interval":3,"offset":0},
+    ///  {"interval":5,"offset":0}]}
+    /// ```
+    ///
+    /// This requires the `serde` feature.
+    #[cfg(feature = "serde")]
+    pub fn to_json_value(&self) -> serde_json::Value {
+        json::to_value(self)
+    }
+
+    /// Construct a chord from the JSON object written by
+    /// `Chord::to_json_value`.
+    ///
+    /// The keys may appear in any order. This requires the `serde` feature.
+    #[cfg(feature = "serde")]
+    pub fn from_json_value(value: serde_json::Value) -> Result<Chord, serde_json::Error> {
+        json::from_value(value)
+    }
+
+    /// Returns the base `NoteClass` of the root note.
+    pub fn root_class(&self) -> NoteClass {
+        self.root.root
+    }
+
+    /// Returns the accidental offset of the root note.
+    pub fn root_offset(&self) -> PitchOffset {
+        self.root.offset
+    }
+
+    /// Return this chord in root position, without any slash root.
+    pub fn root_position(&self) -> Chord {
+        Chord::new(self.root, self.structure.clone())
+    }
+
+    /// Return this chord reduced to its triad, removing the seventh and every
+    /// interval above the fifth.
+    ///
+    /// Added seconds and fourths are kept, as they may stand in for the third,
+    /// as is any slash root. For example, `CMaj13` reduces to `C`.
+    pub fn to_triad(&self) -> Chord {
+        use self::PitchClass::*;
+
+        let structure = [N6, N7, N9, N11, N13].iter()
+            .fold(self.structure.clone(), |structure, &class| structure.remove(class));
+
+        Chord { structure, ..self.clone() }
+    }
+
+    /// Return this chord moved by the given number of semi-tones.
+    ///
+    /// The root and any slash root are respelled as per `Note::transpose`.
+    pub fn transpose(&self, semitones: i32) -> Chord {
+        Chord {
+            slash_root: self.slash_root.map(|n| n.transpose(semitones)),
+            root: self.root.transpose(semitones),
+            structure: self.structure.clone(),
+        }
+    }
+
+    /// Return this chord with its root removed, as played in a rootless
+    /// voicing.
+    ///
+    /// The root is still used to spell the remaining notes, but is no longer
+    /// returned by `iter`. Any slash root is kept. The result is written in
+    /// shorthand with the root omitted, so a rootless `CMaj7` is
+    /// `CMaj7(omit1)`.
+    pub fn rootless(self) -> Chord {
+        Chord { structure: self.structure.remove(PitchClass::N1), ..self }
+    }
+
+    /// Returns this chord in root position followed by each of its
+    /// inversions, written as slash chords.
+    ///
+    /// Each note of the chord other than the root is used as the slash root in
+    /// turn, so `C` gives `C`, `C/E` and `C/G`. Any existing slash root is
+    /// ignored.
+    pub fn as_slash_inversions(&self) -> Vec<Chord> {
+        let root_position = self.root_position();
+        let mut inversions = vec![root_position.clone()];
+
+        for note in root_position.iter() {
+            if note != self.root {
+                inversions.push(Chord::new_slash(note, self.root, self.structure.clone()));
+            }
+        }
+
+        inversions
+    }
+
+    /// Return this chord moved by the given number of semi-tones, with the
+    /// root and any slash root spelled as per `Note::transpose_with`.
+    ///
+    /// The remaining notes follow from the spelling of the root, so with
+    /// `SpellingPreference::Flats` a `C7` moved up a tritone is `Gb7` rather
+    /// than `F#7`.
+    pub fn transpose_in_spelling(&self, semitones: i32, preference: SpellingPreference)
+        -> Chord
+    {
+        Chord {
+            slash_root: self.slash_root.map(|n| n.transpose_with(semitones, preference)),
+            root: self.root.transpose_with(semitones, preference),
+            structure: self.structure.clone(),
+        }
+    }
+
+    /// Return this chord respelled with as few accidentals as possible.
+    ///
+    /// The root is replaced by whichever of its enharmonics (see
+    /// `Note::enharmonics`) gives the fewest accidentals across all the notes
+    /// of the chord, so `A#m` becomes `Bbm`. A slash root which is also a
+    /// note of the chord is spelled to match it, and otherwise is separately
+    /// respelled with the fewest accidentals. The current spelling is kept
+    /// where there is a tie.
+    pub fn preferred_spelling(&self) -> Chord {
+        let accidentals = |root: Note| -> u32 {
+            Chord::new(root, self.structure.clone())
+                .iter()
+                .map(|note| u32::from(note.accidental_count()))
+                .sum()
+        };
+
+        let mut root = self.root;
+        for candidate in self.root.enharmonics() {
+            if accidentals(candidate) < accidentals(root) {
+                root = candidate;
+            }
+        }
+
+        let chord = Chord::new(root, self.structure.clone());
+
+        let slash_root = self.slash_root.map(|slash_root| {
+            let chord_tone = chord.iter().find(|n| n.pitch_class() == slash_root.pitch_class());
+            if let Some(note) = chord_tone {
+                return note;
+            }
+
+            slash_root.enharmonics()
+                .into_iter()
+                .fold(slash_root, |best, candidate| {
+                    if candidate.accidental_count() < best.accidental_count() {
+                        candidate
+                    } else {
+                        best
+                    }
+                })
+        });
+
+        Chord { slash_root, ..chord }
+    }
+
+    /// Return an iterator over each of all notes this chord is comprised of.
+    ///
+    /// Notes are returned from lowest pitch to highest, in order. Each note is
+    /// spelled by its interval above the root, so the spelling follows that of
+    /// the root: the seventh of `Db7` is `Cb` rather than `B`.
+    pub fn iter(&self) -> NoteIterator<'_> {
+        NoteIterator {
+            chord: self,
+            state: NoteIteratorState::Slash,
+        }
+    }
+
+    /// Returns the notes of this chord along with the octave each sounds in.
+    ///
+    /// The root is placed in `base_octave` and every other interval is voiced
+    /// at its full distance above the root, so a ninth lies above the octave.
+    /// A slash root is placed directly below the root. Notes are returned in
+    /// the same order as `iter`.
+    pub fn voicing(&self, base_octave: i8) -> Vec<(Note, i8)> {
+        let root_pitch = self.root.absolute_pitch(base_octave);
+        let mut voicing = Vec::new();
+
+        if let Some(slash) = self.slash_root {
+            let octave = slash.octave_at(root_pitch - 1);
+            voicing.push((slash, octave));
+        }
+
+        for i in 0..PITCH_CLASS_COUNT {
+            if let Some(offset) = self.structure.0[i] {
+                let pc = PitchClass::from_int(i).unwrap();
+                let note = self.root.get_relative((pc, offset));
+                let pitch = root_pitch + pc.to_relative_difference() as i32
+                            + i32::from(offset);
+
+                voicing.push((note, note.octave_at(pitch)));
+            }
+        }
+
+        voicing
+    }
+
+    /// Returns the notes of this chord in closed position, each along with the
+    /// octave it sounds in.
+    ///
+    /// The bass (the slash root if present and otherwise the root) is placed
+    /// in `base_octave` and every other note at its nearest position above
+    /// it, which packs the chord within an octave. Notes sharing a pitch class
+    /// with one already placed are omitted. Notes are returned from lowest to
+    /// highest.
+    pub fn closed_voicing(&self, base_octave: i8) -> Vec<(Note, i8)> {
+        let bass = self.slash_root.unwrap_or(self.root);
+        let bass_pitch = bass.absolute_pitch(base_octave);
+
+        let mut voicing = vec![(bass, base_octave)];
+        let mut pitch_classes = 1 << bass.pitch_class();
+
+        for note in self.iter() {
+            if pitch_classes & (1 << note.pitch_class()) != 0 {
+                continue;
+            }
+            pitch_classes |= 1 << note.pitch_class();
+
+            voicing.push((note, note.octave_at(bass_pitch) + 1));
+        }
+
+        voicing.sort_by_key(|&(note, octave)| note.absolute_pitch(octave));
+        voicing
+    }
+
+    /// Returns the next inversion of this chord beneath a fixed top note, with
+    /// each note along with the octave it sounds in.
+    ///
+    /// The top note is that of `closed_voicing` in `base_octave`. The lowest
+    /// note beneath it moves above the others, and all of them are then packed
+    /// as closely as possible below the top. This suits harmonizing a melody,
+    /// where the top note must stay put: the `C` triad `C4 E4 G4` becomes
+    /// `E3 C4 G4`. Notes are returned from lowest to highest.
+    pub fn invert_keeping_top(&self, base_octave: i8) -> Vec<(Note, i8)> {
+        let mut voicing = self.closed_voicing(base_octave);
+        let top = voicing.pop().unwrap();
+
+        if !voicing.is_empty() {
+            voicing.rotate_left(1);
+        }
+
+        let mut pitch = top.0.absolute_pitch(top.1);
+        let mut inverted = vec![top];
+
+        for &(note, _) in voicing.iter().rev() {
+            let octave = note.octave_at(pitch - 1);
+            pitch = note.absolute_pitch(octave);
+            inverted.push((note, octave));
+        }
+
+        inverted.reverse();
+        inverted
+    }
+
+    /// Returns every way of voicing the notes of this chord between `low` and
+    /// `high` inclusive, each given as a note and its octave.
+    ///
+    /// Each note of the chord, including any slash root, is placed exactly
+    /// once in each voicing, but there are no other constraints on the order
+    /// of the notes. The notes of each voicing are sorted from lowest to
+    /// highest. If a note cannot be placed within the range then there are no
+    /// voicings.
+    pub fn voicings_in_range(&self, low: (Note, i8), high: (Note, i8))
+        -> Vec<Vec<(Note, i8)>>
+    {
+        let low = low.0.absolute_pitch(low.1);
+        let high = high.0.absolute_pitch(high.1);
+
+        let mut notes = Vec::new();
+        for note in self.iter() {
+            if !notes.contains(&note) {
+                notes.push(note);
+            }
+        }
+
+        let mut voicings = vec![Vec::new()];
+
+        for note in notes {
+            let placements: Vec<_> = (note.octave_at(low)..=note.octave_at(high))
+                .filter(|&octave| note.absolute_pitch(octave) >= low
+                                  && note.absolute_pitch(octave) <= high)
+                .map(|octave| (note, octave))
+                .collect();
+
+            voicings = voicings.into_iter()
+                .flat_map(|voicing: Vec<(Note, i8)>| {
+                    placements.iter().map(move |&placement| {
+                        let mut voicing = voicing.clone();
+                        voicing.push(placement);
+                        voicing
+                    })
+                })
+                .collect();
+        }
+
+        for voicing in &mut voicings {
+            voicing.sort_by_key(|&(note, octave)| note.absolute_pitch(octave));
+        }
+
+        voicings
+    }
+
+    /// Returns the number of semi-tones from the lowest to the highest note of
+    /// this chord when voiced as per `voicing`.
+    ///
+    /// A `C` major triad spans `7` semi-tones, while a `C13` with its
+    /// extensions voiced above the octave spans `21`. A chord without any
+    /// notes spans `0`.
+    pub fn span_semitones(&self, base_octave: i8) -> u32 {
+        let pitches: Vec<_> = self.voicing(base_octave)
+            .iter()
+            .map(|&(note, octave)| note.absolute_pitch(octave))
+            .collect();
+
+        match (pitches.iter().min(), pitches.iter().max()) {
+            (Some(lowest), Some(highest)) => (highest - lowest) as u32,
+            _ => 0,
+        }
+    }
+
+    /// Returns the MIDI note numbers of this chord as given by `voicing`.
+    ///
+    /// Any notes which fall outside of the MIDI range are omitted.
+    pub fn to_midi_notes(&self, base_octave: i8) -> Vec<u8> {
+        self.voicing(base_octave)
+            .iter()
+            .filter_map(|&(note, octave)| note.to_midi(octave))
+            .collect()
+    }
+
+    /// Returns an iterator over the notes of this chord as given by `voicing`
+    /// along with their frequencies in hertz.
+    pub fn iter_frequencies(&self, base_octave: i8, a4_hz: f64)
+        -> impl Iterator<Item = (Note, f64)>
+    {
+        self.voicing(base_octave)
+            .into_iter()
+            .map(move |(note, octave)| (note, note.frequency(octave, a4_hz)))
+    }
+
+    /// Returns a Standard MIDI File which plays this chord once for the given
+    /// number of ticks.
+    ///
+    /// The chord is voiced with its root in the fourth octave. See the `midi`
+    /// module for details of the file written, including the limit on
+    /// `duration_ticks`.
+    pub fn to_smf_bytes(&self, duration_ticks: u32) -> Vec<u8> {
+        midi::write_smf(&self.to_midi_notes(4), duration_ticks)
+    }
+
+    /// Returns the intervallic structure of this chord measured from its bass.
+    ///
+    /// For a slash chord this is relative to the slash root, and otherwise it
+    /// is the same as `structure`. Intervals are determined from spelling as
+    /// per `ChordStructure::from_notes`, so `C/E` is a minor third and minor
+    /// sixth above `E`. Should two notes compete for the same interval above
+    /// the bass, the later one in `iter` order is kept.
+    pub fn structure_from_bass(&self) -> ChordStructure {
+        let bass = self.slash_root.unwrap_or(self.root);
+        let notes: Vec<_> = self.iter().collect();
+
+        ChordStructure::from_notes(&bass, &notes).unwrap_or_else(|| {
+            notes.iter().fold(ChordStructure::new(), |structure, note| {
+                let interval = ChordStructure::from_notes(&bass, &[*note]).unwrap();
+                structure.merge(&interval)
+            })
+        })
+    }
+
+    /// Returns whether a note with the same pitch class as `note` is present
+    /// in this chord.
+    ///
+    /// Spelling is ignored, so `C` contains both `E` and `Fb`.
+    pub fn contains_note(&self, note: &Note) -> bool {
+        self.pitch_class_set() & (1 << note.pitch_class()) != 0
+    }
+
+    /// Returns whether a bass note played with the given upper voices implies
+    /// this chord, as when a bassist and pianist share it between them.
+    ///
+    /// The bass must match the slash root, or the root if there is none. The
+    /// voices together must sound every note of the chord and nothing else,
+    /// except that an unaltered fifth may be left out as it adds little to
+    /// the sound. Spelling and doubling are ignored.
+    pub fn implied_by(&self, bass: &Note, upper: &[Note]) -> bool {
+        if bass.pitch_class() != self.slash_root.unwrap_or(self.root).pitch_class() {
+            return false;
+        }
+
+        let played = upper.iter().fold(1 << bass.pitch_class(), |set, note| {
+            set | 1 << note.pitch_class()
+        });
+
+        let set = self.pitch_class_set();
+        let fifth = match self.structure.get(PitchClass::N5) {
+            Some(0) => 1 << self.root.get_relative((PitchClass::N5, 0)).pitch_class(),
+            _ => 0,
+        };
+
+        played == set || played == set & !fifth
+    }
+
+    /// Returns the note of this chord whose pitch class lies the fewest
+    /// semi-tones from `pitch_class` in either direction, with `C` as `0`.
+    ///
+    /// Of equally near notes the first in `iter` order is chosen, so over `C`
+    /// major `F` snaps to `E` while `F#` snaps to `G`. The pitch class is
+    /// reduced to within an octave.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this chord has no notes.
+    pub fn nearest_chord_tone(&self, pitch_class: u8) -> Note {
+        let distance = |note: &Note| {
+            let up = (note.pitch_class() + 12 - pitch_class % 12) % 12;
+            up.min(12 - up)
+        };
+
+        self.iter().min_by_key(distance).unwrap()
+    }
+
+    /// Returns the notes of this chord whose pitch class is not in `scale`,
+    /// in the order given by `iter`.
+    pub fn non_scale_tones(&self, scale: &Scale) -> Vec<Note> {
+        let scale_notes = scale.notes();
+
+        self.iter()
+            .filter(|note| scale_notes.iter().all(|n| n.pitch_class() != note.pitch_class()))
+            .collect()
+    }
+
+    /// Returns whether every note of this chord belongs to the scale of the
+    /// key with the given tonic.
+    ///
+    /// The scale is natural minor if `is_minor` and major otherwise. As with
+    /// `non_scale_tones`, spelling is ignored.
+    pub fn is_diatonic(&self, key: Note, is_minor: bool) -> bool {
+        let mode = if is_minor { Mode::Aeolian } else { Mode::Ionian };
+        self.non_scale_tones(&Scale::new(key, mode)).is_empty()
+    }
+
+    /// Returns the notes of `scale` which lie a semi-tone above a note of this
+    /// chord, the classic "avoid notes" when improvising over it.
+    ///
+    /// Notes which are themselves in the chord are not avoided. Notes are
+    /// returned in scale order, so over `Cmaj7` in `C` major only `F` is
+    /// avoided.
+    pub fn avoid_notes(&self, scale: &Scale) -> Vec<Note> {
+        let set = self.pitch_class_set();
+
+        scale.notes()
+            .into_iter()
+            .filter(|note| {
+                let below = (note.pitch_class() + 11) % 12;
+                set & (1 << note.pitch_class()) == 0 && set & (1 << below) != 0
+            })
+            .collect()
+    }
+
+    /// Returns the name of each distinct pitch class in this chord, in the
+    /// order given by `iter`.
+    ///
+    /// Pitch classes are named independently of how the chord spells them,
+    /// as per `Note::from_pitch_class`, so `Cb` would be named `B`.
+    pub fn pitch_class_names(&self, preference: SpellingPreference) -> Vec<String> {
+        let mut seen = 0u16;
+        let mut names = Vec::new();
+
+        for note in self.iter() {
+            let pitch_class = note.pitch_class();
+
+            if seen & (1 << pitch_class) == 0 {
+                seen |= 1 << pitch_class;
+                names.push(Note::from_pitch_class(pitch_class, preference).to_string());
+            }
+        }
+
+        names
+    }
+
+    /// Returns whether any two adjacent notes of this chord lie a semi-tone or
+    /// a whole tone apart when voiced as per `voicing`.
+    ///
+    /// Since extensions are voiced above the octave, a ninth chord is not a
+    /// cluster but a chord with an added second is. A slash root directly
+    /// below the root, as in `C/B`, also forms one.
+    pub fn has_cluster(&self) -> bool {
+        let mut pitches: Vec<_> = self.voicing(0)
+            .iter()
+            .map(|&(note, octave)| note.absolute_pitch(octave))
+            .collect();
+        pitches.sort();
+
+        pitches.windows(2).any(|pair| matches!(pair[1] - pair[0], 1 | 2))
+    }
+
+    /// Returns the number of semi-tones each note of this chord lies above the
+    /// root, from lowest to highest.
+    ///
+    /// Extensions are not reduced to within an octave, so the ninth of
+    /// `CMaj9` is `14` and the whole chord gives `[0, 4, 7, 11, 14]`. Any
+    /// slash root is ignored.
+    pub fn semitones_from_root(&self) -> Vec<u8> {
+        (0..PITCH_CLASS_COUNT)
+            .filter_map(|i| {
+                self.structure.0[i].map(|offset| {
+                    let pc = PitchClass::from_int(i).unwrap();
+                    (pc.to_relative_difference() as i32 + i32::from(offset)) as u8
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the number of semi-tones between each pair of adjacent notes
+    /// returned by `iter`.
+    ///
+    /// Each gap is measured upwards and reduced to within an octave, so a
+    /// `C` major triad gives `[4, 3]`.
+    pub fn stacked_intervals(&self) -> Vec<u8> {
+        let notes: Vec<_> = self.iter().collect();
+
+        notes.windows(2)
+            .map(|pair| (12 + pair[1].pitch_class() - pair[0].pitch_class()) % 12)
+            .collect()
+    }
+
+    /// Returns the color tones of this chord.
+    ///
+    /// These are the added and extended intervals (the `2`, `4`, `6`, `9`, `11`
+    /// and `13`) along with an altered fifth. The root, third, unaltered fifth
+    /// and seventh are the chord tones and are excluded.
+    pub fn color_tones(&self) -> Vec<Note> {
+        use self::PitchClass::*;
+
+        let mut tones = Vec::new();
+
+        for i in 0..PITCH_CLASS_COUNT {
+            if let Some(offset) = self.structure.0[i] {
+                let pc = PitchClass::from_int(i).unwrap();
+                let color = match pc {
+                    N1 | N3 | N7 => false,
+                    N5 => offset != 0,
+                    N2 | N4 | N6 | N9 | N11 | N13 => true,
+                };
+
+                if color {
+                    tones.push(self.root.get_relative((pc, offset)));
+                }
+            }
+        }
+
+        tones
+    }
+
+    /// Returns the major and minor triads which can be formed entirely from
+    /// the notes of this chord, excluding those built on the root.
+    ///
+    /// These are the upper structures of the chord. For example, a `C13(#11)`
+    /// contains the triads `Gm`, `D` and `Am`.
+    pub fn upper_structures(&self) -> Vec<Chord> {
+        let pitch_classes = self.pitch_class_set();
+        let triads = [ChordStructure::major_triad(), ChordStructure::minor_triad()];
+
+        let mut seen = 1 << self.root.pitch_class();
+        let mut structures = Vec::new();
+
+        for note in self.iter() {
+            if seen & (1 << note.pitch_class()) != 0 {
+                continue;
+            }
+            seen |= 1 << note.pitch_class();
+
+            for triad in &triads {
+                let chord = Chord::new(note, triad.clone());
+                if chord.pitch_class_set() & !pitch_classes == 0 {
+                    structures.push(chord);
+                }
+            }
+        }
+
+        structures
+    }
+
+    /// Returns a measure of the dissonance of this chord.
+    ///
+    /// Each pair of distinct pitch classes in the chord contributes according
+    /// to the interval between them: `2` for a semi-tone or tritone, `1` for a
+    /// whole tone, and nothing for any other interval. A major or minor triad
+    /// has no tension, while a dominant seventh has `3` from its tritone and
+    /// minor seventh.
+    pub fn tension(&self) -> u32 {
+        const WEIGHTS: [u32; 7] = [0, 2, 1, 0, 0, 0, 2];
+
+        let set = self.pitch_class_set();
+        let mut tension = 0;
+
+        for a in 0..12 {
+            for b in a + 1..12 {
+                if set & (1 << a) != 0 && set & (1 << b) != 0 {
+                    let interval = b - a;
+                    tension += WEIGHTS[interval.min(12 - interval)];
+                }
+            }
+        }
+
+        tension
+    }
+
+    /// Returns a measure of how bright (major) or dark (minor) this chord
+    /// sounds, where positive values are brighter.
+    ///
+    /// A major third scores `2` and a minor third `-2`. Every other interval
+    /// scores the number of semi-tones it is raised or lowered from the major
+    /// scale, so a major seventh or a `b5` or `#11` count as `0`, `-1` and `1`
+    /// in turn. `Cmaj7` scores `2`, `C7(b9)` `0` and `Cm7` `-3`.
+    pub fn brightness(&self) -> i32 {
+        (0..PITCH_CLASS_COUNT)
+            .filter_map(|i| self.structure.0[i].map(|offset| (i, i32::from(offset))))
+            .map(|(i, offset)| match PitchClass::from_int(i).unwrap() {
+                PitchClass::N3 => if offset < 0 { -2 } else { 2 },
+                PitchClass::N7 => offset - 1,
+                _ => offset,
+            })
+            .sum()
+    }
+
+    /// Returns whether this chord can function as a dominant, that is it has
+    /// both a major third and a minor seventh.
+    pub fn is_dominant_functioning(&self) -> bool {
+        self.structure.get(PitchClass::N3) == Some(0)
+            && self.structure.get(PitchClass::N7) == Some(0)
+    }
+
+    /// Returns the neo-Riemannian parallel (`P`) of a major or minor triad.
+    ///
+    /// This changes the quality of the triad while keeping its root, so `C`
+    /// becomes `Cm`. Any other chord is returned unchanged.
+    pub fn parallel(&self) -> Chord {
+        match self.triad_quality() {
+            Some(true) => Chord::new(self.root, ChordStructure::minor_triad()),
+            Some(false) => Chord::new(self.root, ChordStructure::major_triad()),
+            None => self.clone(),
+        }
+    }
+
+    /// Returns the neo-Riemannian leading-tone exchange (`L`) of a major or
+    /// minor triad.
+    ///
+    /// A major triad moves its root down a semi-tone, so `C` becomes `Em`.
+    /// Any other chord is returned unchanged.
+    pub fn leading_tone(&self) -> Chord {
+        match self.triad_quality() {
+            Some(true) => Chord::new(
+                self.root.get_relative((PitchClass::N3, 0)),
+                ChordStructure::minor_triad()
+            ),
+            Some(false) => Chord::new(
+                self.root.get_relative((PitchClass::N6, -1)),
+                ChordStructure::major_triad()
+            ),
+            None => self.clone(),
+        }
+    }
+
+    /// Returns the neo-Riemannian relative (`R`) of a major or minor triad.
+    ///
+    /// A major triad moves its fifth up a tone, so `C` becomes `Am`. Any other
+    /// chord is returned unchanged.
+    pub fn relative(&self) -> Chord {
+        match self.triad_quality() {
+            Some(true) => Chord::new(
+                self.root.get_relative((PitchClass::N6, 0)),
+                ChordStructure::minor_triad()
+            ),
+            Some(false) => Chord::new(
+                self.root.get_relative((PitchClass::N3, -1)),
+                ChordStructure::major_triad()
+            ),
+            None => self.clone(),
+        }
+    }
+
+    /// Returns `Some(true)` if this is a major triad, `Some(false)` if it is a
+    /// minor triad and `None` otherwise.
+    fn triad_quality(&self) -> Option<bool> {
+        if self.structure == ChordStructure::major_triad() {
+            Some(true)
+        } else if self.structure == ChordStructure::minor_triad() {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the pitch classes of this chord map onto themselves
+    /// when transposed by some interval smaller than an octave.
+    ///
+    /// The augmented triad and diminished seventh are common examples.
+    pub fn is_symmetric(&self) -> bool {
+        let set = self.pitch_class_set();
+
+        (1..12).any(|n| (set << n | set >> (12 - n)) & 0xfff == set)
+    }
+
+    /// Returns each cyclic rotation of the pitch classes in this chord, with
+    /// `C` as `0`.
+    ///
+    /// The first rotation is the pitch classes sorted in ascending order and
+    /// each subsequent one moves its first pitch class to the end, so `C`
+    /// major gives `[0, 4, 7]`, `[4, 7, 0]` and `[7, 0, 4]`.
+    pub fn rotations(&self) -> Vec<Vec<u8>> {
+        let set = self.pitch_class_set();
+        let classes: Vec<u8> = (0..12).filter(|&pc| set & (1 << pc) != 0).collect();
+
+        (0..classes.len())
+            .map(|i| {
+                let mut rotation = classes.clone();
+                rotation.rotate_left(i);
+                rotation
+            })
+            .collect()
+    }
+
+    /// Returns a measure between `0` and `1` of how strongly the notes of this
+    /// chord imply its root, where higher values are more certain.
+    ///
+    /// The root itself scores `0.2`, a perfect fifth above it `0.5` and a
+    /// major or minor third `0.3`. The score is halved for chords which are
+    /// symmetric (see `Chord::is_symmetric`), since any of their notes could
+    /// serve as the root equally well. A `C` major triad scores `1`, while a
+    /// `Cdim7` scores only `0.25`. Any slash root is ignored.
+    pub fn root_strength(&self) -> f64 {
+        let mut strength = 0.0;
+
+        if self.structure.get(PitchClass::N1).is_some() {
+            strength += 0.2;
+        }
+
+        if self.structure.get(PitchClass::N5) == Some(0) {
+            strength += 0.5;
+        }
+
+        if matches!(self.structure.get(PitchClass::N3), Some(0) | Some(-1)) {
+            strength += 0.3;
+        }
+
+        if self.is_symmetric() {
+            strength /= 2.0;
+        }
+
+        strength
+    }
+
+    /// Returns the set of pitch classes present in this chord as a bitmask,
+    /// with bit `n` set for pitch class `n`.
+    fn pitch_class_set(&self) -> u16 {
+        self.iter().fold(0, |set, note| set | 1 << note.pitch_class())
+    }
+}
+
+impl fmt::Display for Chord {
+    /// Formats the chord in shorthand form, such as `Am7/G`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.root, self.structure)?;
+
+        if let Some(slash_root) = self.slash_root {
+            write!(f, "/{}", slash_root)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a chord from a shorthand string, where a root written in lowercase
+/// marks the chord as minor.
+///
+/// This follows the lead sheet convention where `c` is `Cm`, and is opt-in as
+/// `Chord::from_shorthand` rejects lowercase roots altogether. Chords with an
+/// uppercase root are parsed as usual, so `C` stays major. A quality written
+/// out after a lowercase root is kept, so `caug` is still `Caug`.
+pub fn parse_chord_lowercase_minor(input: &str) -> ParseResult<'_, Chord> {
+    ::parser::parse_chord_lowercase_minor().parse(input).map(|c| c.0)
+}
+
+/// Parse a chord from the longest prefix of `input` which forms a complete
+/// chord, returning it along with the number of bytes it occupies.
+///
+/// This is intended for giving feedback on incomplete input, so no chord and
+/// a length of `0` are returned if no prefix is valid. For example, `Cmaj7 foo`
+/// gives `CMaj7` with a length of `5`, and `C7(b9` gives `C7` with a length
+/// of `2`.
+///
+/// The input is parsed once, stopping where the chord ends. Only if the
+/// parser fails part way through a chord, such as at the end of `C/`, are
+/// shorter prefixes tried in turn.
+pub fn parse_partial(input: &str) -> (Option<Chord>, usize) {
+    let mut end = input.len();
+
+    loop {
+        match ::parser::parse_chord_prefix().parse_stream(&input[..end]) {
+            Ok((chord, remaining)) => return (Some(chord), end - remaining.into_inner().len()),
+            Err(Consumed::Empty(_)) => return (None, 0),
+            Err(Consumed::Consumed(_)) => (),
+        }
+
+        end = (0..end).rev().find(|&end| input.is_char_boundary(end)).unwrap();
+    }
+}
+
+/// The kind of chord a shorthand symbol describes, as told by its separators.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SymbolKind {
+    /// A single chord over its own root, such as `Cmaj7`
+    Plain,
+
+    /// A chord over another bass note, such as `C/E`
+    Slash,
+
+    /// Chords stacked above one another, such as `C|Am`
+    PolyChord,
+}
+
+/// Classify a shorthand symbol by its separators without fully parsing it.
+///
+/// Any `|` makes the symbol a polychord. Otherwise a `/` makes it a slash
+/// chord unless it is followed by a digit, as in `C6/9` and `C/9`, which only
+/// add a tone. The symbol is not checked to be valid, so use
+/// `Chord::from_shorthand` or `PolyChord::from_shorthand` to parse it.
+pub fn classify_symbol(input: &str) -> SymbolKind {
+    if input.contains('|') {
+        return SymbolKind::PolyChord;
+    }
+
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '/' && !chars.peek().is_some_and(char::is_ascii_digit) {
+            return SymbolKind::Slash;
+        }
+    }
+
+    SymbolKind::Plain
+}
+
+impl<'a> TryFrom<&'a [Note]> for Chord {
+    type Error = RecognitionError;
+
+    /// Recognize a chord from its notes as per `Chord::from_notes`.
+    fn try_from(notes: &'a [Note]) -> Result<Chord, RecognitionError> {
+        Chord::from_notes(notes)
+    }
+}
+
+/// Returns the unaltered structures which have a shorthand form.
+#[cfg(any(test, feature = "arbitrary"))]
+fn shorthand_structures() -> Vec<ChordStructure> {
+    use self::ChordExtent::*;
+    use self::ChordQuality::*;
+
+    let mut structures = vec![
+        ChordStructure::new().insert((PitchClass::N5, 0)),
+        ChordStructure::major_triad().insert((PitchClass::N6, 0)),
+        ChordStructure::major_triad().insert((PitchClass::N9, 0)),
+        ChordStructure::minor_triad().insert((PitchClass::N6, 0)).insert((PitchClass::N9, 0)),
+        ChordStructure::new().insert_many(&[(PitchClass::N2, 0), (PitchClass::N5, 0)]),
+        ChordStructure::new().insert_many(&[(PitchClass::N4, 0), (PitchClass::N5, 0)]),
+        ChordStructure::from_quality(Dominant, Seventh)
+            .remove(PitchClass::N3)
+            .insert((PitchClass::N4, 0)),
+    ];
+
+    for &quality in &[Major, Minor, Dominant, Diminished, HalfDiminished, Augmented] {
+        for &extent in &[Triad, Seventh, Ninth, Eleventh, Thirteenth] {
+            // Only a single natural tone may be added to a diminished
+            // seventh, as it has no extended forms.
+            if quality != Diminished || extent as usize <= Ninth as usize {
+                structures.push(ChordStructure::from_quality(quality, extent));
+            }
+        }
+    }
+
+    structures
+}
+
+/// Alters the fifth, ninth, eleventh and thirteenth of a structure from
+/// `shorthand_structures` by the given offsets, with zero leaving the
+/// interval as it is.
+#[cfg(any(test, feature = "arbitrary"))]
+fn alter_shorthand_structure(mut structure: ChordStructure, offsets: [PitchOffset; 4])
+    -> ChordStructure
+{
+    use self::PitchClass::*;
+
+    for (&class, &offset) in [N5, N9, N11, N13].iter().zip(&offsets) {
+        // A diminished seventh cannot be written with an altered fifth.
+        if offset != 0 && (class != N5 || structure.get(N7) != Some(-1)) {
+            structure = structure.insert((class, offset));
+        }
+    }
+
+    structure
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for Note {
+    /// Generates a note which is at most doubly sharpened or flattened.
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Note> {
+        let root = NoteClass::from_int(u.choose_index(NOTE_CLASS_COUNT)?).unwrap();
+        Ok(Note::new(root, u.int_in_range(-2..=2)?))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for Chord {
+    /// Generates a chord which has a shorthand form, so that fuzz targets can
+    /// write it out and parse it back with `Chord::from_shorthand`.
+    ///
+    /// The structure is any quality and extent, or a power, sixth or added
+    /// ninth chord, with its fifth and upper extensions optionally altered.
+    /// A third of chords are given a slash root.
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Chord> {
+        let root = Note::arbitrary(u)?;
+
+        let structure = u.choose(&shorthand_structures())?.clone();
+        let mut offsets = [0; 4];
+        for offset in &mut offsets {
+            *offset = *u.choose(&[0, -1, 1])?;
+        }
+        let structure = alter_shorthand_structure(structure, offsets);
+
+        if u.ratio(1, 3)? {
+            Ok(Chord::new_slash(Note::arbitrary(u)?, root, structure))
+        } else {
+            Ok(Chord::new(root, structure))
+        }
+    }
+}
+
+/// An iterator over notes in a chord.
+///
+/// Notes are returned lowest to highest in pitch.
+#[derive(Clone, Debug)]
+pub struct NoteIterator<'a> {
+    pub chord: &'a Chord,
+    state: NoteIteratorState,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -428,82 +2238,446 @@ impl<'a> Iterator for NoteIterator<'a> {
                         i += 1;
                     }
 
-                    self.state = Exhausted;
-                    None
-                },
+                    self.state = Exhausted;
+                    None
+                },
+
+                Exhausted => {
+                    None
+                },
+            };
+        }
+    }
+}
+
+/// A single polychord which is comprised of two or more chords stacked atop
+/// one another.
+///
+/// There are always at least two layers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolyChord {
+    /// Chord layers, from the uppermost down to the lowest
+    layers: Vec<Chord>,
+}
+
+impl PolyChord {
+    /// Construct and return a new `PolyChord` of two layers.
+    pub fn new(upper: Chord, lower: Chord) -> PolyChord {
+        PolyChord { layers: vec![upper, lower] }
+    }
+
+    /// Construct a `PolyChord` from its layers, from the uppermost down.
+    ///
+    /// Returns `None` if fewer than two layers are given.
+    pub fn from_layers(layers: Vec<Chord>) -> Option<PolyChord> {
+        if layers.len() >= 2 { Some(PolyChord { layers }) } else { None }
+    }
+
+    /// Construct a chord from a shorthand string.
+    pub fn from_shorthand(input: &str) -> ParseResult<'_, PolyChord> {
+        parse_polychord().parse(input).map(|c| c.0)
+    }
+
+    /// Returns the chord layers, from the uppermost down to the lowest.
+    pub fn layers(&self) -> &[Chord] {
+        &self.layers
+    }
+
+    /// Returns the uppermost chord layer.
+    pub fn upper(&self) -> &Chord {
+        &self.layers[0]
+    }
+
+    /// Returns the lowest chord layer.
+    pub fn lower(&self) -> &Chord {
+        &self.layers[self.layers.len() - 1]
+    }
+
+    /// Return an iterator over each of all notes this chord is comprised of.
+    ///
+    /// Notes are returned from lowest pitch to highest, in order, with each
+    /// layer in turn starting from the lowest.
+    pub fn iter(&self) -> impl Iterator<Item = Note> + '_ {
+        self.layers.iter().rev().flat_map(Chord::iter)
+    }
+}
+
+impl fmt::Display for PolyChord {
+    /// Formats the polychord in shorthand form, such as `D|C`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let layers: Vec<_> = self.layers.iter().map(Chord::to_string).collect();
+        f.write_str(&layers.join("|"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    use chord::NoteClass::*;
+    use chord::PitchClass::*;
+
+    #[test]
+    fn offset_calculation() {
+        assert_eq!(Note::new(A, 0).get_relative((N5, 0)), Note::new(E, 0));
+        assert_eq!(Note::new(A, 0).get_relative((N5, -1)), Note::new(E, -1));
+        assert_eq!(Note::new(F, -1).get_relative((N2, -2)), Note::new(G, -3));
+        assert_eq!(Note::new(D, 0).get_relative((N3, 0)), Note::new(F, 1));
+        assert_eq!(Note::new(A, 0).get_relative((N3, 0)), Note::new(C, 1));
+        assert_eq!(Note::new(C, 0).get_relative((N9, 0)), Note::new(D, 0));
+        assert_eq!(Note::new(C, 0).get_relative((N13, -1)), Note::new(A, -1));
+    }
+
+    #[test]
+    fn note_accidental_count() {
+        assert_eq!(Note::new(C, 0).accidental_count(), 0);
+        assert_eq!(Note::new(F, 2).accidental_count(), 2);
+        assert_eq!(Note::new(B, -1).accidental_count(), 1);
+        assert_eq!(Note::new(E, -2).accidental_count(), 2);
+    }
+
+    #[test]
+    fn note_absolute_pitch() {
+        assert_eq!(Note::new(C, 0).absolute_pitch(4), 48);
+        assert!(Note::new(C, 0).absolute_pitch(5) > Note::new(B, 0).absolute_pitch(4));
+        assert_eq!(Note::new(C, 1).absolute_pitch(4), Note::new(D, -1).absolute_pitch(4));
+        assert_eq!(Note::new(B, 1).absolute_pitch(4), Note::new(C, 0).absolute_pitch(5));
+        assert_eq!(Note::new(C, -1).absolute_pitch(4), Note::new(B, 0).absolute_pitch(3));
+    }
+
+    #[test]
+    fn note_to_midi() {
+        assert_eq!(Note::new(C, 0).to_midi(4), Some(60));
+        assert_eq!(Note::new(A, 0).to_midi(4), Some(69));
+        assert_eq!(Note::new(C, 0).to_midi(-1), Some(0));
+        assert_eq!(Note::new(C, -1).to_midi(-1), None);
+        assert_eq!(Note::new(G, 0).to_midi(9), Some(127));
+        assert_eq!(Note::new(A, 0).to_midi(9), None);
+    }
+
+    #[test]
+    fn note_frequency() {
+        assert_eq!(Note::new(A, 0).frequency(4, 440.0), 440.0);
+        assert_eq!(Note::new(A, 0).frequency(5, 440.0), 880.0);
+        assert_eq!(Note::new(A, 0).frequency(4, 432.0), 432.0);
+        assert!((Note::new(C, 0).frequency(4, 440.0) - 261.626).abs() < 1e-3);
+    }
+
+    #[test]
+    fn note_enharmonics() {
+        let expected = vec![Note::new(E, 2), Note::new(F, 1), Note::new(G, -1)];
+        assert_eq!(Note::new(F, 1).enharmonics(), expected);
+
+        let expected = vec![Note::new(C, 1), Note::new(D, -1), Note::new(B, 2)];
+        assert_eq!(Note::new(D, -1).enharmonics(), expected);
+
+        let expected = vec![Note::new(C, 0), Note::new(D, -2), Note::new(B, 1)];
+        assert_eq!(Note::new(C, 0).enharmonics(), expected);
+    }
+
+    #[test]
+    fn note_transpose() {
+        assert_eq!(Note::new(C, 0).transpose(7), Note::new(G, 0));
+        assert_eq!(Note::new(E, -1).transpose(-1), Note::new(D, 0));
+        assert_eq!(Note::new(E, -1).transpose(3), Note::new(G, -1));
+        assert_eq!(Note::new(F, 1).transpose(2), Note::new(G, 1));
+        assert_eq!(Note::new(C, 0).transpose(-2), Note::new(B, -1));
+        assert_eq!(Note::new(B, 1).transpose(12), Note::new(C, 0));
+    }
+
+    #[test]
+    fn chord_notes() {
+        // A/C#
+        let chord = Chord::new_slash(
+            Note::new(C, 1),
+            Note::new(A, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, 0), (N5, 0)])
+        );
+
+        let notes = vec![
+            Note::new(C, 1),
+            Note::new(A, 0),
+            Note::new(C, 1),
+            Note::new(E, 0),
+        ];
+
+        assert_eq!(chord.iter().collect::<Vec<_>>(), notes);
+    }
+
+    #[test]
+    fn chord_voicing() {
+        let chord = Chord::from_shorthand("Bb9/D").unwrap();
+        let expected = vec![
+            (Note::new(D, 0), 3),
+            (Note::new(B, -1), 3),
+            (Note::new(D, 0), 4),
+            (Note::new(F, 0), 4),
+            (Note::new(A, -1), 4),
+            (Note::new(C, 0), 5),
+        ];
+
+        assert_eq!(chord.voicing(3), expected);
+    }
+
+    #[test]
+    fn chord_to_midi_notes() {
+        let chord = Chord::from_shorthand("C").unwrap();
+        assert_eq!(chord.to_midi_notes(4), vec![60, 64, 67]);
+
+        let chord = Chord::from_shorthand("C/E").unwrap();
+        assert_eq!(chord.to_midi_notes(4), vec![52, 60, 64, 67]);
+
+        let chord = Chord::from_shorthand("C9").unwrap();
+        assert_eq!(chord.to_midi_notes(4), vec![60, 64, 67, 70, 74]);
+
+        let chord = Chord::from_shorthand("G").unwrap();
+        assert_eq!(chord.to_midi_notes(9), vec![127]);
+    }
+
+    #[test]
+    fn chord_iter_frequencies() {
+        let chord = Chord::from_shorthand("C").unwrap();
+        let frequencies: Vec<_> = chord.iter_frequencies(4, 440.0).collect();
+        let expected = [(C, 261.63), (E, 329.63), (G, 392.00)];
+
+        assert_eq!(frequencies.len(), expected.len());
+        for (&(note, hz), &(root, expected)) in frequencies.iter().zip(expected.iter()) {
+            assert_eq!(note, Note::new(root, 0));
+            assert!((hz - expected).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn repeated_alteration_last_wins() {
+        let chord = |symbol| Chord::from_shorthand(symbol).unwrap();
+
+        assert_eq!(chord("C7(b9,#9)"), chord("C7(#9)"));
+        assert_eq!(chord("C7(b5,#5)"), chord("Caug7"));
+    }
+
+    #[test]
+    fn chord_to_smf_bytes() {
+        let bytes = Chord::from_shorthand("Cmaj7").unwrap().to_smf_bytes(480);
+        assert!(bytes.starts_with(b"MThd"));
+
+        let note_ons = bytes.windows(3)
+            .filter(|w| w[0] == 0x90 && w[2] == 64)
+            .count();
+        assert_eq!(note_ons, 4);
+    }
+
+    #[test]
+    fn chord_stacked_intervals() {
+        let intervals = |symbol| Chord::from_shorthand(symbol).unwrap().stacked_intervals();
+
+        assert_eq!(intervals("C"), vec![4, 3]);
+        assert_eq!(intervals("CMaj7"), vec![4, 3, 4]);
+        assert_eq!(intervals("C/E"), vec![8, 4, 3]);
+        assert_eq!(intervals("C9"), vec![4, 3, 3, 4]);
+    }
+
+    #[test]
+    fn chord_color_tones() {
+        let chord = Chord::from_shorthand("C13").unwrap();
+        let expected = vec![Note::new(D, 0), Note::new(F, 0), Note::new(A, 0)];
+        assert_eq!(chord.color_tones(), expected);
+
+        let chord = Chord::from_shorthand("C7(#5,b9)").unwrap();
+        let expected = vec![Note::new(G, 1), Note::new(D, -1)];
+        assert_eq!(chord.color_tones(), expected);
+
+        let chord = Chord::from_shorthand("Cm7").unwrap();
+        assert_eq!(chord.color_tones(), vec![]);
+    }
+
+    #[test]
+    fn chord_from_quality() {
+        use super::ChordQuality::*;
+        use super::ChordExtent::*;
+
+        let cases = [
+            (Dominant, Seventh, "C7"),
+            (Major, Triad, "C"),
+            (Major, Ninth, "CMaj9"),
+            (Minor, Eleventh, "Cm11"),
+            (Dominant, Thirteenth, "C13"),
+            (Diminished, Seventh, "Cdim7"),
+            (HalfDiminished, Seventh, "Cm7(b5)"),
+            (Augmented, Triad, "C+"),
+            (Augmented, Seventh, "C+7"),
+        ];
+
+        for &(quality, extent, symbol) in &cases {
+            let chord = Chord::from_quality(Note::new(C, 0), quality, extent);
+            assert_eq!(chord, Chord::from_shorthand(symbol).unwrap(), "{}", symbol);
+        }
+    }
+
+    #[test]
+    fn structure_bytes_round_trip() {
+        let structures = [
+            ChordStructure::new(),
+            ChordStructure::default(),
+            ChordStructure::major_triad(),
+            ChordStructure::new()
+                .insert_many(&[(N3, 0), (N5, 1), (N7, 1), (N9, -1), (N11, 1), (N13, 0)]),
+            ChordStructure::new()
+                .insert_many(&[(N2, 127), (N4, -128), (N6, -1), (N13, 2)]),
+            ChordStructure::from_component((N13, -7)),
+        ];
+
+        for structure in &structures {
+            assert_eq!(&ChordStructure::from_bytes(structure.to_bytes()), structure);
+        }
+    }
+
+    #[test]
+    fn structure_bytes_layout() {
+        let structure = ChordStructure::new()
+            .insert_many(&[(N3, -1), (N5, -1), (N13, 1)]);
+        let expected = [0b0001_0101, 0b0000_0010, 0, 0, 0xff, 0, 0xff, 0, 0, 0, 0, 1];
+
+        assert_eq!(structure.to_bytes(), expected);
+    }
+
+    #[test]
+    fn structure_from_notes() {
+        let notes = [Note::new(C, 0), Note::new(E, -1), Note::new(G, 1), Note::new(D, 1)];
+        let expected = ChordStructure::new()
+            .insert_many(&[(N3, -1), (N5, 1), (N9, 1)]);
+        assert_eq!(ChordStructure::from_notes(&Note::new(C, 0), &notes), Some(expected));
+
+        let notes = [Note::new(C, 0), Note::new(F, 0), Note::new(B, -1)];
+        let expected = ChordStructure::new()
+            .insert_many(&[(N7, 0), (N11, 0)]);
+        assert_eq!(ChordStructure::from_notes(&Note::new(C, 0), &notes), Some(expected));
+
+        let notes = [Note::new(C, 0), Note::new(G, 0), Note::new(G, 1)];
+        assert_eq!(ChordStructure::from_notes(&Note::new(C, 0), &notes), None);
+    }
+
+    #[test]
+    fn chord_from_notes() {
+        let notes = [Note::new(C, 0), Note::new(E, 0), Note::new(G, 0), Note::new(B, -1)];
+        assert_eq!(Chord::from_notes(&notes).ok(), Chord::from_shorthand("C7").ok());
+
+        let notes = [Note::new(E, 0), Note::new(G, 0), Note::new(C, 0)];
+        assert_eq!(Chord::from_notes(&notes).ok(), Chord::from_shorthand("C/E").ok());
+
+        let notes = [Note::new(B, 0), Note::new(D, 0), Note::new(F, 0), Note::new(A, -1)];
+        assert_eq!(Chord::from_notes(&notes).ok(), Chord::from_shorthand("Bdim7").ok());
+    }
+
+    #[test]
+    fn chord_from_notes_errors() {
+        let notes = [Note::new(C, 0)];
+        assert_eq!(Chord::from_notes(&notes), Err(RecognitionError::TooFewNotes));
+        assert_eq!(Chord::from_notes(&[]), Err(RecognitionError::TooFewNotes));
+
+        let notes = [Note::new(C, 0), Note::new(D, -1), Note::new(D, 0)];
+        assert_eq!(Chord::from_notes(&notes), Err(RecognitionError::NoMatchingTemplate));
+
+        // Both C6/E and Am7/E
+        let notes = [Note::new(E, 0), Note::new(C, 0), Note::new(G, 0), Note::new(A, 0)];
+        assert_eq!(Chord::from_notes(&notes), Err(RecognitionError::AmbiguousRoot));
+    }
+
+    #[test]
+    fn chord_try_from_notes() {
+        let notes = [Note::new(C, 0), Note::new(E, 0), Note::new(G, 0)];
+        assert_eq!(Chord::try_from(&notes[..]), Ok(Chord::from_shorthand("C").unwrap()));
+
+        assert_eq!(Chord::try_from(&notes[..1]), Err(RecognitionError::TooFewNotes));
+    }
+
+    #[test]
+    fn chord_root_accessors() {
+        let chord = Chord::from_shorthand("Bbm7").unwrap();
+        assert_eq!(chord.root_class(), B);
+        assert_eq!(chord.root_offset(), -1);
+    }
+
+    #[test]
+    fn seventh_chord_notes() {
+        let dominant = Chord::from_shorthand("C7").unwrap();
+        let notes = vec![
+            Note::new(C, 0),
+            Note::new(E, 0),
+            Note::new(G, 0),
+            Note::new(B, -1),
+        ];
+        assert_eq!(dominant.iter().collect::<Vec<_>>(), notes);
+
+        let major = Chord::from_shorthand("CMaj7").unwrap();
+        assert_eq!(major.iter().last(), Some(Note::new(B, 0)));
 
-                Exhausted => {
-                    None
-                },
-            };
-        }
+        let diminished = Chord::from_shorthand("Cdim7").unwrap();
+        assert_eq!(diminished.iter().last(), Some(Note::new(B, -2)));
     }
-}
 
-/// A single polychord which is comprised of an upper chord stacked atop a
-/// lower chord.
-#[derive(Clone, Debug, PartialEq)]
-pub struct PolyChord {
-    /// Upper chord structure
-    pub upper: Chord,
+    #[test]
+    fn upper_structures() {
+        let chord = Chord::from_shorthand("C13(#11)").unwrap();
 
-    /// Lower chord structure
-    pub lower: Chord
-}
+        let expected = vec![
+            Chord::from_shorthand("Gm").unwrap(),
+            Chord::from_shorthand("D").unwrap(),
+            Chord::from_shorthand("Am").unwrap(),
+        ];
 
-impl PolyChord {
-    /// Construct and return a new `PolyChord`.
-    pub fn new(upper: Chord, lower: Chord) -> PolyChord {
-        PolyChord { upper, lower }
+        assert_eq!(chord.upper_structures(), expected);
     }
 
-    /// Construct a chord from a shorthand string.
-    pub fn from_shorthand(input: &str) -> ParseResult<PolyChord> {
-        parse_polychord().parse(input).map(|c| c.0)
+    #[test]
+    fn chord_is_dominant_functioning() {
+        let functioning = |symbol| {
+            Chord::from_shorthand(symbol).unwrap().is_dominant_functioning()
+        };
+
+        assert!(functioning("C7"));
+        assert!(functioning("C13(b9)"));
+        assert!(functioning("C+7"));
+        assert!(!functioning("CMaj7"));
+        assert!(!functioning("C"));
+        assert!(!functioning("Cm7"));
     }
 
-    /// Return an iterator over each of all notes this chord is comprised of.
-    ///
-    /// Notes are returned from lowest pitch to highest, in order.
-    pub fn iter(&self) -> iter::Chain<NoteIterator, NoteIterator> {
-        self.lower.iter().chain(self.upper.iter())
+    #[test]
+    fn chord_is_symmetric() {
+        let symmetric = |symbol| Chord::from_shorthand(symbol).unwrap().is_symmetric();
+
+        assert!(symmetric("Cdim7"));
+        assert!(symmetric("C+"));
+        assert!(symmetric("C7(b5)"));
+        assert!(!symmetric("CMaj7"));
+        assert!(!symmetric("Cdim"));
+        assert!(!symmetric("C"));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn neo_riemannian_transformations() {
+        let c = Chord::from_shorthand("C").unwrap();
 
-    use chord::NoteClass::*;
-    use chord::PitchClass::*;
+        assert_eq!(c.parallel(), Chord::from_shorthand("Cm").unwrap());
+        assert_eq!(c.relative(), Chord::from_shorthand("Am").unwrap());
+        assert_eq!(c.leading_tone(), Chord::from_shorthand("Em").unwrap());
 
-    #[test]
-    fn offset_calculation() {
-        assert_eq!(Note::new(A, 0).get_relative((N5, 0)), Note::new(E, 0));
-        assert_eq!(Note::new(A, 0).get_relative((N5, -1)), Note::new(E, -1));
-        assert_eq!(Note::new(F, -1).get_relative((N2, -2)), Note::new(G, -3));
-        assert_eq!(Note::new(D, 0).get_relative((N3, 0)), Note::new(F, 1));
-        assert_eq!(Note::new(A, 0).get_relative((N3, 0)), Note::new(C, 1));
+        // Each transformation is an involution
+        assert_eq!(c.parallel().parallel(), c);
+        assert_eq!(c.relative().relative(), c);
+        assert_eq!(c.leading_tone().leading_tone(), c);
     }
 
     #[test]
-    fn chord_notes() {
-        // A/C#
-        let chord = Chord::new_slash(
-            Note::new(C, 1),
-            Note::new(A, 0),
-            ChordStructure::new()
-                .insert_many(&[(N3, 0), (N5, 0)])
-        );
-
-        let notes = vec![
-            Note::new(C, 1),
-            Note::new(A, 0),
-            Note::new(C, 1),
-            Note::new(E, 0),
-        ];
+    fn neo_riemannian_non_triad() {
+        let chord = Chord::from_shorthand("C7").unwrap();
 
-        assert_eq!(chord.iter().collect::<Vec<_>>(), notes);
+        assert_eq!(chord.parallel(), chord);
+        assert_eq!(chord.relative(), chord);
+        assert_eq!(chord.leading_tone(), chord);
     }
 
     #[test]
@@ -566,4 +2740,621 @@ mod tests {
         let expected = PolyChord::new(upper, lower);
         assert_eq!(chord, expected);
     }
+
+    #[test]
+    fn polychord_layers() {
+        let c = Chord::from_shorthand("C").unwrap();
+        let d = Chord::from_shorthand("D").unwrap();
+        let g = Chord::from_shorthand("G").unwrap();
+
+        let chord = PolyChord::from_layers(vec![d.clone(), c.clone(), g.clone()]).unwrap();
+        assert_eq!(chord.layers(), &[d.clone(), c.clone(), g.clone()][..]);
+        assert_eq!(chord.upper(), &d);
+        assert_eq!(chord.lower(), &g);
+
+        assert_eq!(PolyChord::from_layers(vec![c]), None);
+        assert_eq!(PolyChord::from_layers(vec![]), None);
+    }
+
+    #[test]
+    fn chord_display() {
+        let symbols = [
+            "C", "Cm", "C5", "Cdim", "Cdim7", "Caug", "Caug7", "C6", "Cm6",
+            "C6/9", "C/9", "C7", "CMaj7", "Cm7", "CmMaj7", "Cm7(b5)", "C9",
+            "CMaj9", "Cm11", "C13(#11)", "C7(b5,#9)", "Caug7(b9)", "Bb7/D", "F#m7/C#",
+            "Csus4", "Csus2", "C7sus4", "C9sus4(b5)",
+        ];
+
+        for symbol in &symbols {
+            assert_eq!(Chord::from_shorthand(symbol).unwrap().to_string(), *symbol);
+        }
+
+        assert_eq!(PolyChord::from_shorthand("D|C").unwrap().to_string(), "D|C");
+    }
+
+    /// Returns a strategy generating the chords which have a shorthand form,
+    /// above roots which are at most doubly sharpened or flattened.
+    fn shorthand_chords() -> impl Strategy<Value = Chord> {
+        let note = (0..NOTE_CLASS_COUNT, -2..=2 as PitchOffset)
+            .prop_map(|(class, offset)| Note::new(NoteClass::from_int(class).unwrap(), offset));
+        let offset = prop::sample::select(vec![0, -1, 1]);
+
+        (
+            note.clone(),
+            prop::sample::select(shorthand_structures()),
+            prop::array::uniform4(offset),
+            prop::option::weighted(0.3, note),
+        )
+            .prop_map(|(root, structure, offsets, slash_root)| {
+                let structure = alter_shorthand_structure(structure, offsets);
+
+                match slash_root {
+                    Some(slash_root) => Chord::new_slash(slash_root, root, structure),
+                    None => Chord::new(root, structure),
+                }
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn chord_display_round_trip(chord in shorthand_chords()) {
+            let symbol = chord.to_string();
+            prop_assert_eq!(Chord::from_shorthand(&symbol).unwrap(), chord, "{}", symbol);
+        }
+    }
+
+    /// Returns a fixed stream of bytes in the manner of fuzzer input.
+    #[cfg(feature = "arbitrary")]
+    fn fuzz_bytes() -> Vec<u8> {
+        (0..4096u32).map(|i| (i.wrapping_mul(0x9e37_79b9) >> 24) as u8).collect()
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_chords() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let bytes = fuzz_bytes();
+        let mut u = Unstructured::new(&bytes);
+
+        while !u.is_empty() {
+            let chord = Chord::arbitrary(&mut u).unwrap();
+
+            assert_eq!(chord.structure.get(N1), Some(0));
+            assert!(chord.root.offset.abs() <= 2);
+            assert_eq!(chord.iter().next(), Some(chord.slash_root.unwrap_or(chord.root)));
+
+            let symbol = chord.to_string();
+            assert_eq!(Chord::from_shorthand(&symbol).unwrap(), chord, "{}", symbol);
+        }
+    }
+
+    #[test]
+    fn chord_structure_from_bass() {
+        let chord = Chord::from_shorthand("C/E").unwrap();
+        let expected = ChordStructure::new()
+            .insert_many(&[(N3, -1), (N6, -1)]);
+
+        assert_eq!(chord.structure_from_bass(), expected);
+
+        let chord = Chord::from_shorthand("Am7").unwrap();
+        assert_eq!(chord.structure_from_bass(), chord.structure);
+    }
+
+    #[test]
+    fn chord_rootless() {
+        let chord = Chord::from_shorthand("CMaj7").unwrap().rootless();
+
+        let notes = vec![Note::new(E, 0), Note::new(G, 0), Note::new(B, 0)];
+        assert_eq!(chord.iter().collect::<Vec<_>>(), notes);
+    }
+
+    #[test]
+    fn structure_quality() {
+        use chord::ChordQuality::*;
+
+        let quality = |symbol| Chord::from_shorthand(symbol).unwrap().structure.quality();
+
+        assert_eq!(quality("C"), Some(Major));
+        assert_eq!(quality("CMaj9"), Some(Major));
+        assert_eq!(quality("C13"), Some(Dominant));
+        assert_eq!(quality("CmMaj7"), Some(Minor));
+        assert_eq!(quality("Cdim7"), Some(Diminished));
+        assert_eq!(quality("Cm7(b5)"), Some(HalfDiminished));
+        assert_eq!(quality("Caug7"), Some(Augmented));
+        assert_eq!(quality("C5"), None);
+        assert_eq!(quality("C7(b5)"), None);
+    }
+
+    #[test]
+    fn micro_note_frequency() {
+        let a4 = Note::new(A, 0);
+
+        let quarter_sharp = MicroNote::new(a4, 50);
+        assert!((quarter_sharp.frequency(4, 440.0) - 452.893).abs() < 0.001);
+
+        // A quarter-tone is half way to the next semi-tone in pitch.
+        let semitone = Note::new(A, 1).frequency(4, 440.0);
+        let midpoint = (440.0 * semitone).sqrt();
+        assert!((quarter_sharp.frequency(4, 440.0) - midpoint).abs() < 1e-9);
+
+        assert_eq!(MicroNote::from(a4).frequency(4, 440.0), 440.0);
+    }
+
+    #[test]
+    fn structure_validate() {
+        let structure = ChordStructure::from_quality(ChordQuality::Major, ChordExtent::Seventh);
+        assert_eq!(structure.validate(), Ok(()));
+
+        let conflicting = structure.clone().insert_many(&[(N2, 0), (N9, -1)]);
+        assert_eq!(conflicting.validate(), Err(StructureError::ConflictingInterval(N2, N9)));
+
+        let rootless = structure.remove(N1);
+        assert_eq!(rootless.validate(), Err(StructureError::MissingRoot));
+    }
+
+    #[test]
+    fn chord_as_slash_inversions() {
+        let inversions = Chord::from_shorthand("C").unwrap().as_slash_inversions();
+
+        let expected: Vec<_> = ["C", "C/E", "C/G"].iter()
+            .map(|symbol| Chord::from_shorthand(symbol).unwrap())
+            .collect();
+
+        assert_eq!(inversions, expected);
+    }
+
+    #[test]
+    fn note_spelling_preference() {
+        use chord::SpellingPreference::*;
+
+        assert_eq!(Note::from_pitch_class(6, Sharps), Note::new(F, 1));
+        assert_eq!(Note::from_pitch_class(6, Flats), Note::new(G, -1));
+        assert_eq!(Note::from_pitch_class(3, Auto), Note::new(E, -1));
+
+        assert_eq!(Note::new(C, 0).transpose_with(6, Flats), Note::new(G, -1));
+        assert_eq!(Note::new(C, 0).transpose_with(6, Sharps), Note::new(F, 1));
+        assert_eq!(Note::new(G, 2).simplify(Auto), Note::new(A, 0));
+        assert_eq!(Note::new(E, 1).simplify(Flats), Note::new(F, 0));
+        assert_eq!(Note::new(A, 1).simplify(Flats), Note::new(B, -1));
+    }
+
+    #[test]
+    fn chord_tension() {
+        let tension = |symbol| Chord::from_shorthand(symbol).unwrap().tension();
+
+        assert_eq!(tension("C"), 0);
+        assert_eq!(tension("Am"), 0);
+        assert_eq!(tension("G7"), 3);
+        assert_eq!(tension("CMaj7"), 2);
+        assert_eq!(tension("Cdim7"), 4);
+        assert_eq!(tension("C7(b9)"), 7);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn chord_json_round_trip() {
+        let chord = Chord::from_shorthand("C/E").unwrap();
+        let json = "{\"root\":\"C\",\"accidental\":0,\
+                    \"slash_root\":{\"root\":\"E\",\"accidental\":0},\
+                    \"intervals\":[{\"interval\":1,\"offset\":0},\
+                    {\"interval\":3,\"offset\":0},{\"interval\":5,\"offset\":0}]}";
+
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(chord.to_json_value(), value);
+        assert_eq!(Chord::from_json_value(value).unwrap(), chord);
+
+        for symbol in &["Bbm7(b5)", "F#Maj13(#11)", "C5", "Dbdim7/Fb", "CMaj7(omit1)"] {
+            let chord = Chord::from_shorthand(symbol).unwrap();
+            assert_eq!(Chord::from_json_value(chord.to_json_value()).unwrap(), chord);
+        }
+
+        let reordered = serde_json::from_str("{ \"intervals\": [ { \"offset\": 0, \"interval\": 1 } ],
+                                                \"slash_root\": null, \"accidental\": -1,
+                                                \"root\": \"A\" }").unwrap();
+        let expected = Chord::new(Note::new(A, -1), ChordStructure::new());
+        assert_eq!(Chord::from_json_value(reordered).unwrap(), expected);
+
+        let unknown = serde_json::from_str("{\"root\":\"H\",\"accidental\":0,\
+                                             \"slash_root\":null,\"intervals\":[]}").unwrap();
+        assert!(Chord::from_json_value(unknown).is_err());
+    }
+
+    #[test]
+    fn chord_transpose_in_spelling() {
+        use chord::SpellingPreference::*;
+
+        let chord = Chord::from_shorthand("C7").unwrap();
+
+        assert_eq!(chord.transpose_in_spelling(6, Flats), Chord::from_shorthand("Gb7").unwrap());
+        assert_eq!(chord.transpose_in_spelling(6, Sharps), Chord::from_shorthand("F#7").unwrap());
+
+        let slash = Chord::from_shorthand("C/E").unwrap();
+        assert_eq!(slash.transpose_in_spelling(1, Flats), Chord::from_shorthand("Db/F").unwrap());
+    }
+
+    #[test]
+    fn note_letter_distance() {
+        assert_eq!(Note::new(C, 0).letter_distance(&Note::new(E, 0)), 2);
+        assert_eq!(Note::new(E, 0).letter_distance(&Note::new(C, 0)), -2);
+        assert_eq!(Note::new(C, 1).letter_distance(&Note::new(B, -1)), 6);
+        assert_eq!(Note::new(A, 0).letter_distance(&Note::new(A, 1)), 0);
+        assert_eq!(Note::new(A, 0).letter_distance(&Note::new(D, 0)), -4);
+    }
+
+    #[test]
+    fn chord_voicings_in_range() {
+        let chord = Chord::from_shorthand("C").unwrap();
+
+        let voicings = chord.voicings_in_range((Note::new(C, 0), 4), (Note::new(C, 0), 5));
+        let expected = vec![
+            vec![(Note::new(C, 0), 4), (Note::new(E, 0), 4), (Note::new(G, 0), 4)],
+            vec![(Note::new(E, 0), 4), (Note::new(G, 0), 4), (Note::new(C, 0), 5)],
+        ];
+        assert_eq!(voicings, expected);
+
+        let voicings = chord.voicings_in_range((Note::new(C, 0), 4), (Note::new(B, 0), 5));
+        assert_eq!(voicings.len(), 8);
+
+        let voicings = chord.voicings_in_range((Note::new(F, 0), 4), (Note::new(B, 0), 4));
+        assert!(voicings.is_empty());
+    }
+
+    #[test]
+    fn chord_closed_voicing() {
+        let chord = Chord::from_shorthand("CMaj9").unwrap();
+        let voicing = chord.closed_voicing(4);
+
+        let expected = vec![
+            (Note::new(C, 0), 4), (Note::new(D, 0), 4), (Note::new(E, 0), 4),
+            (Note::new(G, 0), 4), (Note::new(B, 0), 4),
+        ];
+        assert_eq!(voicing, expected);
+
+        let pitches: Vec<_> = Chord::from_shorthand("CMaj7").unwrap()
+            .closed_voicing(4)
+            .iter()
+            .map(|&(note, octave)| note.absolute_pitch(octave))
+            .collect();
+        assert!(pitches.last().unwrap() - pitches[0] < 12);
+
+        let voicing = Chord::from_shorthand("C/G").unwrap().closed_voicing(3);
+        let expected = vec![(Note::new(G, 0), 3), (Note::new(C, 0), 4), (Note::new(E, 0), 4)];
+        assert_eq!(voicing, expected);
+    }
+
+    #[test]
+    fn chord_flat_spelling_is_consistent() {
+        let chord = Chord::from_shorthand("Db7").unwrap();
+
+        let notes = vec![Note::new(D, -1), Note::new(F, 0), Note::new(A, -1), Note::new(C, -1)];
+        assert_eq!(chord.iter().collect::<Vec<_>>(), notes);
+
+        let notes = vec![Note::new(E, -1), Note::new(G, 0), Note::new(B, -1), Note::new(D, -1)];
+        assert_eq!(chord.transpose(2).iter().collect::<Vec<_>>(), notes);
+
+        let notes = vec![Note::new(G, -1), Note::new(B, -1), Note::new(D, -1), Note::new(F, -1)];
+        assert_eq!(chord.transpose(5).iter().collect::<Vec<_>>(), notes);
+    }
+
+    #[test]
+    fn chord_contains_note() {
+        let chord = Chord::from_shorthand("C").unwrap();
+
+        assert!(chord.contains_note(&Note::new(E, 0)));
+        assert!(chord.contains_note(&Note::new(F, -1)));
+        assert!(!chord.contains_note(&Note::new(F, 1)));
+    }
+
+    #[test]
+    fn chord_preferred_spelling() {
+        let respelled = |symbol| {
+            Chord::from_shorthand(symbol).unwrap().preferred_spelling().to_string()
+        };
+
+        assert_eq!(respelled("A#m"), "Bbm");
+        assert_eq!(respelled("E#7"), "F7");
+        assert_eq!(respelled("Fb/Ab"), "E/G#");
+        assert_eq!(respelled("Fb/Bbb"), "E/A");
+        assert_eq!(respelled("Gb"), "Gb");
+        assert_eq!(respelled("F#"), "F#");
+    }
+
+    #[test]
+    fn templates_parse_from_name() {
+        for (name, structure) in TEMPLATES {
+            let chord = Chord::from_shorthand(&format!("C{}", name)).unwrap();
+
+            assert_eq!(chord.structure, *structure, "{}", name);
+            assert_eq!(structure.to_string(), *name);
+        }
+    }
+
+    #[test]
+    fn chord_root_position() {
+        let chord = Chord::from_shorthand("C/E").unwrap();
+        assert_eq!(chord.root_position(), Chord::from_shorthand("C").unwrap());
+    }
+
+    #[test]
+    fn chord_to_triad() {
+        let triad = |symbol| Chord::from_shorthand(symbol).unwrap().to_triad().to_string();
+
+        assert_eq!(triad("CMaj13"), "C");
+        assert_eq!(triad("Am7/G"), "Am/G");
+        assert_eq!(triad("C7(#5,b9)"), "Caug");
+        assert_eq!(triad("C6/9"), "C");
+    }
+
+    #[test]
+    fn chord_non_scale_tones() {
+        use scale::Mode;
+
+        let major = Scale::new(Note::new(C, 0), Mode::Ionian);
+        let chord = Chord::from_shorthand("C7(#11)").unwrap();
+        assert_eq!(chord.non_scale_tones(&major), vec![Note::new(B, -1), Note::new(F, 1)]);
+
+        let lydian = Scale::new(Note::new(C, 0), Mode::Lydian);
+        let chord = Chord::from_shorthand("CMaj7(#11)").unwrap();
+        assert!(chord.non_scale_tones(&lydian).is_empty());
+    }
+
+    #[test]
+    fn chord_pitch_class_names() {
+        use chord::SpellingPreference::*;
+
+        let chord = Chord::from_shorthand("C7").unwrap();
+        assert_eq!(chord.pitch_class_names(Flats), ["C", "E", "G", "Bb"]);
+        assert_eq!(chord.pitch_class_names(Sharps), ["C", "E", "G", "A#"]);
+
+        let chord = Chord::from_shorthand("Db7/Db").unwrap();
+        assert_eq!(chord.pitch_class_names(Sharps), ["C#", "F", "G#", "B"]);
+    }
+
+    #[test]
+    fn chord_parse_partial() {
+        let cmaj7 = Chord::from_shorthand("CMaj7").unwrap();
+
+        assert_eq!(parse_partial("Cmaj7 foo"), (Some(cmaj7.clone()), 5));
+        assert_eq!(parse_partial("Cmaj7"), (Some(cmaj7), 5));
+        assert_eq!(parse_partial("C7(b9"), (Some(Chord::from_shorthand("C7").unwrap()), 2));
+        assert_eq!(parse_partial("Cm7/"), (Some(Chord::from_shorthand("Cm7").unwrap()), 3));
+        assert_eq!(parse_partial("Am/Ebx"), (Some(Chord::from_shorthand("Am/Eb").unwrap()), 5));
+        assert_eq!(parse_partial("Δ"), (None, 0));
+        assert_eq!(parse_partial(""), (None, 0));
+    }
+
+    #[test]
+    fn invert_keeping_top() {
+        let c = Chord::from_shorthand("C").unwrap();
+        let top = *c.closed_voicing(4).last().unwrap();
+
+        let inverted = c.invert_keeping_top(4);
+        assert_eq!(inverted.last(), Some(&top));
+        assert_eq!(inverted, vec![(Note::new(E, 0), 3), (Note::new(C, 0), 4), top]);
+
+        let c7 = Chord::from_shorthand("C7").unwrap();
+        let inverted = c7.invert_keeping_top(4);
+        assert_eq!(inverted.last(), c7.closed_voicing(4).last());
+        assert_eq!(inverted[0], (Note::new(E, 0), 3));
+        assert!(inverted.windows(2).all(|pair| {
+            pair[0].0.absolute_pitch(pair[0].1) < pair[1].0.absolute_pitch(pair[1].1)
+        }));
+
+        let single = Chord::new(Note::new(C, 0), ChordStructure::new().insert((PitchClass::N1, 0)));
+        assert_eq!(single.invert_keeping_top(4), vec![(Note::new(C, 0), 4)]);
+    }
+
+    #[test]
+    fn brightness() {
+        let brightness = |symbol| Chord::from_shorthand(symbol).unwrap().brightness();
+
+        assert_eq!(brightness("Cmaj7"), 2);
+        assert_eq!(brightness("Cm7"), -3);
+        assert_eq!(brightness("C7(b9)"), 0);
+        assert!(brightness("Cmaj7") > brightness("Cm7"));
+        assert!(brightness("Cmaj7") > brightness("C7(b9)"));
+        assert!(brightness("CMaj7(#11)") > brightness("Cmaj7"));
+    }
+
+    #[test]
+    fn power_chord() {
+        let e5 = Chord::power_chord(Note::new(E, 0));
+
+        assert_eq!(e5.iter().collect::<Vec<_>>(), vec![Note::new(E, 0), Note::new(B, 0)]);
+        assert_eq!(e5, Chord::from_shorthand("E5").unwrap());
+        assert_eq!(e5.to_string(), "E5");
+    }
+
+    #[test]
+    fn avoid_notes() {
+        use scale::Mode;
+
+        let c_major = Scale::new(Note::new(C, 0), Mode::Ionian);
+
+        let cmaj7 = Chord::from_shorthand("Cmaj7").unwrap();
+        assert_eq!(cmaj7.avoid_notes(&c_major), vec![Note::new(F, 0)]);
+
+        let g7 = Chord::from_shorthand("G7").unwrap();
+        assert_eq!(g7.avoid_notes(&c_major), vec![Note::new(C, 0)]);
+    }
+
+    #[test]
+    fn rotations() {
+        let c = Chord::from_shorthand("C").unwrap();
+        assert_eq!(c.rotations(), vec![vec![0, 4, 7], vec![4, 7, 0], vec![7, 0, 4]]);
+
+        let g7 = Chord::from_shorthand("G7").unwrap();
+        assert_eq!(g7.rotations().len(), 4);
+        assert_eq!(g7.rotations()[0], vec![2, 5, 7, 11]);
+    }
+
+    #[test]
+    fn is_diatonic() {
+        let c = Note::new(C, 0);
+
+        assert!(Chord::from_shorthand("Dm7").unwrap().is_diatonic(c, false));
+        assert!(!Chord::from_shorthand("D7").unwrap().is_diatonic(c, false));
+        assert!(Chord::from_shorthand("Bb").unwrap().is_diatonic(c, true));
+        assert!(!Chord::from_shorthand("Bb").unwrap().is_diatonic(c, false));
+    }
+
+    #[test]
+    fn has_cluster() {
+        let cluster = Chord::new(
+            Note::new(C, 0),
+            ChordStructure::major_triad().insert((PitchClass::N2, 0))
+        );
+
+        assert!(cluster.has_cluster());
+        assert!(Chord::from_shorthand("C/B").unwrap().has_cluster());
+        assert!(!Chord::from_shorthand("C").unwrap().has_cluster());
+        assert!(!Chord::from_shorthand("C9").unwrap().has_cluster());
+    }
+
+    #[test]
+    fn from_intervals() {
+        let c = Note::new(C, 0);
+        let chord = |symbol| Some(Chord::from_shorthand(symbol).unwrap());
+
+        assert_eq!(Chord::from_intervals(c, &[0, 4, 7, 11]), chord("CMaj7"));
+        assert_eq!(Chord::from_intervals(c, &[0, 3, 7, 10]), chord("Cm7"));
+        assert_eq!(Chord::from_intervals(c, &[0, 4, 8]), chord("Caug"));
+        assert_eq!(Chord::from_intervals(c, &[0, 4, 7, 10, 15]), chord("C7(#9)"));
+        assert_eq!(Chord::from_intervals(c, &[0, 4, 7, 10, 14]), chord("C9"));
+        assert_eq!(Chord::from_intervals(c, &[0, 7, 16, 22]), chord("C7"));
+        assert_eq!(Chord::from_intervals(c, &[0, 3, 4, 7]), None);
+    }
+
+    #[test]
+    fn span_semitones() {
+        let span = |symbol| Chord::from_shorthand(symbol).unwrap().span_semitones(4);
+
+        assert_eq!(span("C"), 7);
+        assert_eq!(span("C13"), 21);
+        assert_eq!(span("C/E"), 15);
+        assert!(span("C13") > span("CMaj7"));
+
+        let empty = Chord::new(Note::new(C, 0), ChordStructure::default());
+        assert_eq!(empty.span_semitones(4), 0);
+    }
+
+    #[test]
+    fn black_keys() {
+        assert!(!Note::new(C, 0).is_black_key());
+        assert!(Note::new(C, 1).is_black_key());
+        assert!(!Note::new(B, 1).is_black_key());
+        assert!(!Note::new(C, -1).is_black_key());
+        assert!(Note::new(B, -1).is_black_key());
+        assert!(!Note::new(G, 2).is_black_key());
+    }
+
+    #[test]
+    fn canonical_symbol() {
+        let canonical = |symbol| Chord::from_shorthand(symbol).unwrap().canonical_symbol();
+
+        for symbol in &["Cmin7", "Cm7", "C-7", "Cmi7", "CMIN7"] {
+            assert_eq!(canonical(symbol), "Cm7");
+        }
+
+        for symbol in &["Cmaj7", "CMaj7", "CM7", "CΔ7", "CΔ"] {
+            assert_eq!(canonical(symbol), "CMaj7");
+        }
+
+        assert_eq!(canonical("C°7"), canonical("Cdim7"));
+        assert_eq!(canonical("C major"), "C");
+    }
+
+    #[test]
+    fn split_extensions() {
+        let structure = Chord::from_shorthand("CMaj13").unwrap().structure;
+        let (triad, extensions) = structure.split_extensions();
+
+        assert_eq!(triad, ChordStructure::major_triad());
+        assert_eq!(extensions.get(PitchClass::N1), None);
+        assert_eq!(extensions.get(PitchClass::N7), Some(1));
+        assert_eq!(extensions.get(PitchClass::N13), Some(0));
+        assert_eq!(triad.merge(&extensions), structure);
+
+        let (_, extensions) = ChordStructure::minor_triad().split_extensions();
+        assert_eq!(extensions, ChordStructure::new().remove(PitchClass::N1));
+    }
+
+    #[test]
+    fn implied_by() {
+        let cmaj7 = Chord::from_shorthand("Cmaj7").unwrap();
+        let c = Note::new(C, 0);
+
+        assert!(cmaj7.implied_by(&c, &[Note::new(E, 0), Note::new(G, 0), Note::new(B, 0)]));
+        assert!(cmaj7.implied_by(&c, &[Note::new(B, 0), Note::new(E, 0)]));
+        assert!(!cmaj7.implied_by(&c, &[Note::new(E, 0), Note::new(G, 0)]));
+        assert!(!cmaj7.implied_by(&Note::new(E, 0), &[Note::new(G, 0), Note::new(B, 0), c]));
+        assert!(!cmaj7.implied_by(&c, &[Note::new(E, 0), Note::new(B, -1)]));
+
+        let c_over_e = Chord::from_shorthand("C/E").unwrap();
+        assert!(c_over_e.implied_by(&Note::new(E, 0), &[c, Note::new(G, 0)]));
+    }
+
+    #[test]
+    fn nearest_chord_tone() {
+        let c = Chord::from_shorthand("C").unwrap();
+
+        assert_eq!(c.nearest_chord_tone(5), Note::new(E, 0));
+        assert_eq!(c.nearest_chord_tone(6), Note::new(G, 0));
+        assert_eq!(c.nearest_chord_tone(8), Note::new(G, 0));
+        assert_eq!(c.nearest_chord_tone(11), Note::new(C, 0));
+        assert_eq!(c.nearest_chord_tone(2), Note::new(C, 0));
+        assert_eq!(c.nearest_chord_tone(19), Note::new(G, 0));
+    }
+
+    #[test]
+    fn classify_symbols() {
+        assert_eq!(classify_symbol("Cmaj7"), SymbolKind::Plain);
+        assert_eq!(classify_symbol("C6/9"), SymbolKind::Plain);
+        assert_eq!(classify_symbol("C/9"), SymbolKind::Plain);
+        assert_eq!(classify_symbol("C/E"), SymbolKind::Slash);
+        assert_eq!(classify_symbol("C6/9/E"), SymbolKind::Slash);
+        assert_eq!(classify_symbol("C|Am"), SymbolKind::PolyChord);
+        assert_eq!(classify_symbol("A/C#|Gm/Bb"), SymbolKind::PolyChord);
+    }
+
+    #[test]
+    fn semitones_from_root() {
+        let semitones = |symbol| Chord::from_shorthand(symbol).unwrap().semitones_from_root();
+
+        assert_eq!(semitones("Cmaj9"), vec![0, 4, 7, 11, 14]);
+        assert_eq!(semitones("Am7(b5)"), vec![0, 3, 6, 10]);
+        assert_eq!(semitones("C/E"), vec![0, 4, 7]);
+    }
+
+    #[test]
+    fn root_strength() {
+        let strength = |symbol| Chord::from_shorthand(symbol).unwrap().root_strength();
+
+        assert!((strength("C") - 1.0).abs() < 1e-9);
+        assert!((strength("Cdim7") - 0.25).abs() < 1e-9);
+        assert!(strength("C") > strength("Cdim7"));
+        assert!(strength("Cm7") > strength("Caug"));
+        assert!(Chord::from_shorthand("C").unwrap().rootless().root_strength() < strength("C"));
+    }
+
+    #[test]
+    fn display_omitted_tones() {
+        let chord = |symbol| Chord::from_shorthand(symbol).unwrap();
+
+        let rootless = chord("CMaj7").rootless();
+        assert_eq!(rootless.to_string(), "CMaj7(omit1)");
+        assert_eq!(chord("CMaj7(omit1)"), rootless);
+
+        let rootless = chord("C7(b9)/E").rootless();
+        assert_eq!(rootless.to_string(), "C7(b9)(omit1)/E");
+        assert_eq!(chord("C7(b9)(omit1)/E"), rootless);
+
+        let empty = Chord::new(Note::new(C, 0), ChordStructure::default());
+        assert_eq!(empty.to_string(), "C5(omit1,5)");
+        assert_eq!(chord("C5(omit1,5)"), empty);
+
+        assert_eq!(chord("C7(omit5)").to_string(), "C7(omit5)");
+        assert_eq!(chord("C(omit3)"), chord("C5"));
+    }
 }
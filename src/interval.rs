@@ -0,0 +1,256 @@
+//! Types representing the intervals between notes.
+
+use std::fmt;
+
+use chord::*;
+
+/// The quality of an interval, describing how it differs from the major or
+/// perfect interval of the same number.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum IntervalQuality {
+    Diminished, Minor, Perfect, Major, Augmented
+}
+
+/// A simple interval, named by its quality and number such as a major third.
+///
+/// Only valid intervals can be constructed, so the number always lies from
+/// `1` to `7` and the quality suits it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Interval {
+    /// How the interval is altered
+    quality: IntervalQuality,
+
+    /// The number of letter names spanned, from `1` (unison) to `7`
+    number: u8,
+}
+
+/// The semi-tones spanned by the major or perfect interval of each number.
+const NATURAL_SEMITONES: [i32; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+impl Interval {
+    /// Construct and return a new `Interval`.
+    ///
+    /// Returns `None` unless `number` is a simple interval from `1` to `7`
+    /// and `quality` suits it: unisons, fourths and fifths are never major or
+    /// minor, the others are never perfect, and there is no diminished unison.
+    pub fn new(quality: IntervalQuality, number: u8) -> Option<Interval> {
+        use self::IntervalQuality::*;
+
+        let interval = Interval { quality, number };
+
+        let valid = match quality {
+            _ if !(1..=7).contains(&number) => false,
+            Diminished => number != 1,
+            Perfect => interval.is_perfect_number(),
+            Major | Minor => !interval.is_perfect_number(),
+            Augmented => true,
+        };
+
+        if valid { Some(interval) } else { None }
+    }
+
+    /// Returns the quality of this interval.
+    pub fn quality(&self) -> IntervalQuality {
+        self.quality
+    }
+
+    /// Returns the number of this interval, from `1` (unison) to `7`.
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    /// Returns the ascending simple interval from `from` up to `to`.
+    ///
+    /// The number is determined from the spelling of the notes, so `C` to `E`
+    /// is a major third while `C` to `Fb` is a diminished fourth. Notes which
+    /// are altered further than augmented or diminished are clamped to those
+    /// qualities.
+    pub fn between(from: &Note, to: &Note) -> Interval {
+        use self::IntervalQuality::*;
+
+        let number = from.letter_distance(to).rem_euclid(7) as usize;
+        let semitones = i32::from(to.pitch_class()) - i32::from(from.pitch_class());
+        let alteration = (semitones - NATURAL_SEMITONES[number] + 6).rem_euclid(12) - 6;
+
+        let interval = Interval { quality: Perfect, number: number as u8 + 1 };
+        let quality = match alteration {
+            0 if interval.is_perfect_number() => Perfect,
+            -1 if interval.is_perfect_number() => Diminished,
+            0 => Major,
+            -1 => Minor,
+            _ if alteration < 0 => Diminished,
+            _ => Augmented,
+        };
+
+        Interval { quality, ..interval }
+    }
+
+    /// Returns whether this interval is a unison, fourth or fifth, which are
+    /// perfect rather than major or minor when unaltered.
+    fn is_perfect_number(&self) -> bool {
+        matches!(self.number, 1 | 4 | 5)
+    }
+
+    /// Returns the number of semi-tones this interval spans.
+    pub fn semitones(&self) -> u8 {
+        use self::IntervalQuality::*;
+
+        let natural = NATURAL_SEMITONES[usize::from(self.number - 1)];
+        let alteration = match self.quality {
+            Perfect | Major => 0,
+            Minor => -1,
+            Diminished if self.is_perfect_number() => -1,
+            Diminished => -2,
+            Augmented => 1,
+        };
+
+        (natural + alteration).rem_euclid(12) as u8
+    }
+
+    /// Returns the inversion of this interval, which together with it spans
+    /// an octave.
+    ///
+    /// For example, a perfect fourth inverts to a perfect fifth and a minor
+    /// third to a major sixth. A unison inverts to itself.
+    pub fn invert(&self) -> Interval {
+        use self::IntervalQuality::*;
+
+        let quality = match self.quality {
+            Diminished => Augmented,
+            Minor => Major,
+            Perfect => Perfect,
+            Major => Minor,
+            Augmented => Diminished,
+        };
+
+        let number = if self.number == 1 { 1 } else { 9 - self.number };
+        Interval { quality, number }
+    }
+}
+
+/// Returns the note the given interval above `root`, spelled as the interval
+/// names it.
+///
+/// Unlike `Note::get_relative`, which is given an interval of a chord, this
+/// takes a named interval, so a major third above `C` is `E` and an augmented
+/// second is `D#`.
+pub fn note_from_interval(root: &Note, interval: Interval) -> Note {
+    let class = (root.root.to_int() + usize::from(interval.number) - 1) % NOTE_CLASS_COUNT;
+    let natural = Note::new(NoteClass::from_int(class).unwrap(), 0);
+
+    let pitch_class = i32::from(root.pitch_class()) + i32::from(interval.semitones());
+    let offset = (pitch_class - i32::from(natural.pitch_class()) + 6).rem_euclid(12) - 6;
+
+    Note::new(natural.root, offset as PitchOffset)
+}
+
+/// Returns whether the interval between two notes is consonant.
+///
+/// Unisons, octaves, perfect fifths and major or minor thirds and sixths are
+/// consonant, as are perfect fourths since they invert to fifths, so the
+/// order of the notes doesn't matter. Seconds, sevenths and any augmented or
+/// diminished interval, including the tritone, are dissonant. The interval
+/// is found from spelling, so `C` to `Fb` is a dissonant diminished fourth
+/// even though it sounds as a major third.
+pub fn is_consonant(a: &Note, b: &Note) -> bool {
+    use self::IntervalQuality::*;
+
+    let interval = Interval::between(a, b);
+
+    match interval.quality {
+        Perfect => true,
+        Major | Minor => matches!(interval.number, 3 | 6),
+        Diminished | Augmented => false,
+    }
+}
+
+impl fmt::Display for Interval {
+    /// Writes the interval in its short form, such as `P5` or `m3`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::IntervalQuality::*;
+
+        let quality = match self.quality {
+            Diminished => 'd',
+            Minor => 'm',
+            Perfect => 'P',
+            Major => 'M',
+            Augmented => 'A',
+        };
+
+        write!(f, "{}{}", quality, self.number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::IntervalQuality::*;
+
+    use chord::NoteClass::*;
+
+    fn interval(quality: IntervalQuality, number: u8) -> Interval {
+        Interval::new(quality, number).unwrap()
+    }
+
+    #[test]
+    fn intervals_between_notes() {
+        let c = Note::new(C, 0);
+
+        assert_eq!(Interval::between(&c, &Note::new(C, 0)), interval(Perfect, 1));
+        assert_eq!(Interval::between(&c, &Note::new(E, 0)), interval(Major, 3));
+        assert_eq!(Interval::between(&c, &Note::new(E, -1)), interval(Minor, 3));
+        assert_eq!(Interval::between(&c, &Note::new(F, 1)), interval(Augmented, 4));
+        assert_eq!(Interval::between(&c, &Note::new(G, -1)), interval(Diminished, 5));
+        assert_eq!(Interval::between(&c, &Note::new(B, 0)), interval(Major, 7));
+        assert_eq!(Interval::between(&Note::new(B, 0), &c), interval(Minor, 2));
+        assert_eq!(Interval::between(&Note::new(G, 0), &c), interval(Perfect, 4));
+
+        assert_eq!(interval(Perfect, 4).invert(), interval(Perfect, 5));
+        assert_eq!(interval(Minor, 3).invert(), interval(Major, 6));
+        assert_eq!(interval(Augmented, 4).semitones(), 6);
+        assert_eq!(interval(Diminished, 7).semitones(), 9);
+        assert_eq!(interval(Minor, 6).to_string(), "m6");
+    }
+
+    #[test]
+    fn invalid_intervals() {
+        assert_eq!(Interval::new(Major, 0), None);
+        assert_eq!(Interval::new(Major, 9), None);
+        assert_eq!(Interval::new(Perfect, 3), None);
+        assert_eq!(Interval::new(Minor, 5), None);
+        assert_eq!(Interval::new(Diminished, 1), None);
+        assert_eq!(Interval::new(Augmented, 1).map(|i| i.semitones()), Some(1));
+    }
+
+    #[test]
+    fn notes_from_intervals() {
+        let c = Note::new(C, 0);
+
+        assert_eq!(note_from_interval(&c, interval(Major, 3)), Note::new(E, 0));
+        assert_eq!(note_from_interval(&c, interval(Minor, 3)), Note::new(E, -1));
+        assert_eq!(note_from_interval(&c, interval(Augmented, 2)), Note::new(D, 1));
+        assert_eq!(note_from_interval(&Note::new(B, 0), interval(Minor, 2)), Note::new(C, 0));
+        assert_eq!(note_from_interval(&Note::new(E, -1), interval(Perfect, 5)),
+                   Note::new(B, -1));
+
+        let f_sharp = Note::new(F, 1);
+        let diminished = interval(Diminished, 7);
+        assert_eq!(Interval::between(&f_sharp, &note_from_interval(&f_sharp, diminished)),
+                   diminished);
+    }
+
+    #[test]
+    fn consonance() {
+        let c = Note::new(C, 0);
+
+        assert!(is_consonant(&c, &Note::new(E, 0)));
+        assert!(is_consonant(&c, &Note::new(G, 0)));
+        assert!(is_consonant(&c, &Note::new(A, -1)));
+        assert!(is_consonant(&Note::new(G, 0), &c));
+        assert!(!is_consonant(&c, &Note::new(F, 1)));
+        assert!(!is_consonant(&c, &Note::new(G, -1)));
+        assert!(!is_consonant(&c, &Note::new(D, 0)));
+        assert!(!is_consonant(&c, &Note::new(B, 0)));
+        assert!(!is_consonant(&c, &Note::new(F, -1)));
+    }
+}
@@ -0,0 +1,86 @@
+//! Conversion of chords to and from a structured JSON form.
+//!
+//! This is only available with the `serde` feature. See `Chord::to_json_value`
+//! for a description of the object written.
+
+use serde::de::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::{self, Value};
+
+use chord::*;
+
+/// A note written as its letter name and accidental.
+#[derive(Serialize, Deserialize)]
+struct JsonNote {
+    root: String,
+    accidental: PitchOffset,
+}
+
+/// A single interval of a chord structure.
+#[derive(Serialize, Deserialize)]
+struct JsonInterval {
+    interval: u8,
+    offset: PitchOffset,
+}
+
+/// A chord as written to JSON.
+#[derive(Serialize, Deserialize)]
+struct JsonChord {
+    #[serde(flatten)]
+    root: JsonNote,
+    slash_root: Option<JsonNote>,
+    intervals: Vec<JsonInterval>,
+}
+
+impl JsonNote {
+    fn from_note(note: &Note) -> JsonNote {
+        JsonNote { root: note.root.to_string(), accidental: note.offset }
+    }
+
+    fn to_note(&self) -> Result<Note, serde_json::Error> {
+        let mut chars = self.root.chars();
+
+        match (chars.next().and_then(NoteClass::from_char), chars.next()) {
+            (Some(root), None) => Ok(Note::new(root, self.accidental)),
+            _ => Err(serde_json::Error::custom(format!("unknown note `{}`", self.root))),
+        }
+    }
+}
+
+/// Returns the JSON object describing `chord`.
+pub fn to_value(chord: &Chord) -> Value {
+    let intervals = (0..PITCH_CLASS_COUNT)
+        .map(|i| PitchClass::from_int(i).unwrap())
+        .filter_map(|class| chord.structure.get(class).map(|offset| {
+            JsonInterval { interval: class.number(), offset }
+        }))
+        .collect();
+
+    let json = JsonChord {
+        root: JsonNote::from_note(&chord.root),
+        slash_root: chord.slash_root.as_ref().map(JsonNote::from_note),
+        intervals,
+    };
+
+    serde_json::to_value(json).unwrap()
+}
+
+/// Returns the chord described by a JSON object written by `to_value`.
+pub fn from_value(value: Value) -> Result<Chord, serde_json::Error> {
+    let json: JsonChord = serde_json::from_value(value)?;
+
+    let mut structure = ChordStructure::default();
+    for interval in &json.intervals {
+        let class = PitchClass::from_number(interval.interval).ok_or_else(|| {
+            serde_json::Error::custom(format!("unknown interval `{}`", interval.interval))
+        })?;
+        structure = structure.insert((class, interval.offset));
+    }
+
+    let root = json.root.to_note()?;
+
+    Ok(match json.slash_root {
+        Some(ref slash_root) => Chord::new_slash(slash_root.to_note()?, root, structure),
+        None => Chord::new(root, structure),
+    })
+}
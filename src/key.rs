@@ -0,0 +1,384 @@
+//! Functions relating notes and chords to a key.
+//!
+//! A key is identified by its tonic `Note`. Unless stated otherwise keys are
+//! taken to be major.
+
+use chord::*;
+use scale::{Mode, Scale};
+
+use combine::Parser;
+
+/// The role a chord plays within a key.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HarmonicFunction {
+    Tonic, Subdominant, Dominant
+}
+
+/// Returns the dominant (fifth degree) of the key.
+pub fn dominant(key: Note) -> Note {
+    key.get_relative((PitchClass::N5, 0))
+}
+
+/// Returns the subdominant (fourth degree) of the key.
+pub fn subdominant(key: Note) -> Note {
+    key.get_relative((PitchClass::N4, 0))
+}
+
+/// Construct a chord from a shorthand string whose slash root may be given
+/// as a scale degree of `key`.
+///
+/// The degree is written as a roman numeral relative to the major scale with
+/// any accidentals before it, so `C/bVII` in `C` has a `Bb` bass. A slash
+/// root may still be given as a note.
+pub fn parse_chord_in_key(input: &str, key: Note) -> ParseResult<'_, Chord> {
+    ::parser::parse_chord_in_key(key).parse(input).map(|c| c.0)
+}
+
+/// Returns the secondary dominant of a chord.
+///
+/// This is the dominant seventh chord built a fifth above the root of
+/// `target`, which resolves to it (`V7/x`).
+pub fn secondary_dominant(target: &Chord) -> Chord {
+    Chord::from_quality(
+        dominant(target.root),
+        ChordQuality::Dominant,
+        ChordExtent::Seventh
+    )
+}
+
+/// Returns the secondary leading-tone chord of a chord.
+///
+/// This is the diminished seventh chord built on the leading tone of
+/// `target`, a semi-tone below its root, which resolves to it (`vii°7/x`).
+/// The root is spelled as the seventh of the target's scale, so the chord
+/// for `Dm` is `C#dim7` rather than `Dbdim7`.
+pub fn secondary_leading_tone(target: &Chord) -> Chord {
+    Chord::from_quality(
+        target.root.get_relative((PitchClass::N7, 1)),
+        ChordQuality::Diminished,
+        ChordExtent::Seventh
+    )
+}
+
+/// Returns the chords commonly borrowed into the key from its parallel minor.
+///
+/// These are the `iv`, `bVI`, `bVII`, `bIII`, `ii°` and `v` triads of the
+/// parallel minor (`Mode::Aeolian`), in roughly decreasing order of how often
+/// they are used. In `C` these are `Fm`, `Ab`, `Bb`, `Eb`, `Ddim` and `Gm`.
+pub fn modal_interchange(key: Note) -> Vec<Chord> {
+    const DEGREES: [usize; 6] = [3, 5, 6, 2, 1, 4];
+
+    let triads = Scale::new(key, Mode::Aeolian).harmonize(ChordExtent::Triad);
+    DEGREES.iter().map(|&degree| triads[degree].clone()).collect()
+}
+
+/// Returns the triads diatonic to both of the major keys `from` and `to`,
+/// which can serve as pivot chords when modulating between them.
+///
+/// The chords are given in scale order of `from`, so `C` to `G` gives `C`,
+/// `Em`, `G` and `Am`.
+pub fn pivot_chords(from: Note, to: Note) -> Vec<Chord> {
+    key_scale(from, false).harmonize(ChordExtent::Triad)
+        .into_iter()
+        .filter(|chord| chord.is_diatonic(to, false))
+        .collect()
+}
+
+/// Returns the scale of the key, which is `Mode::Aeolian` if `is_minor` and
+/// `Mode::Ionian` otherwise.
+fn key_scale(tonic: Note, is_minor: bool) -> Scale {
+    Scale::new(tonic, if is_minor { Mode::Aeolian } else { Mode::Ionian })
+}
+
+/// Returns the key signature of the key as a signed count of accidentals,
+/// with sharps positive and flats negative.
+///
+/// For example, `G` major is `1`, `F` major is `-1` and `C` minor is `-3`.
+/// Keys beyond seven sharps or flats, such as `G#` major, count each double
+/// accidental twice.
+pub fn key_signature(tonic: Note, is_minor: bool) -> i8 {
+    key_scale(tonic, is_minor).notes()
+        .iter()
+        .map(|note| note.offset)
+        .sum()
+}
+
+/// Returns the notes altered by the key signature of the key, in the order
+/// they are written in the signature.
+///
+/// Sharps are written in the order `F C G D A E B` and flats in the order
+/// `B E A D G C F`, so `G` major gives `[F#]` and `Eb` major gives
+/// `[Bb, Eb, Ab]`.
+pub fn accidental_notes(tonic: Note, is_minor: bool) -> Vec<Note> {
+    use chord::NoteClass::*;
+
+    let mut notes: Vec<_> = key_scale(tonic, is_minor).notes()
+        .into_iter()
+        .filter(|note| note.offset != 0)
+        .collect();
+
+    let order = if key_signature(tonic, is_minor) > 0 {
+        [F, C, G, D, A, E, B]
+    } else {
+        [B, E, A, D, G, C, F]
+    };
+
+    notes.sort_by_key(|note| order.iter().position(|&class| class == note.root));
+    notes
+}
+
+/// Returns the harmonic function of a chord in the key.
+///
+/// The function is determined by the scale degree of the chord's root:
+/// `I`, `iii` and `vi` are tonic, `ii` and `IV` are subdominant, and `V` and
+/// `vii` are dominant. Returns `None` if the root is not in the key.
+pub fn harmonic_function(chord: &Chord, key: Note) -> Option<HarmonicFunction> {
+    use self::HarmonicFunction::*;
+
+    const FUNCTIONS: [HarmonicFunction; 7] = [
+        Tonic, Subdominant, Tonic, Subdominant, Dominant, Tonic, Dominant,
+    ];
+
+    key_scale(key, false).notes()
+        .iter()
+        .position(|note| note.pitch_class() == chord.root.pitch_class())
+        .map(|degree| FUNCTIONS[degree])
+}
+
+/// Returns whether both chords have the same harmonic function in the key,
+/// as per `harmonic_function`.
+///
+/// Chords rooted outside of the key have no function, so never share one.
+pub fn same_function(a: &Chord, b: &Chord, key: Note) -> bool {
+    match (harmonic_function(a, key), harmonic_function(b, key)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Returns the degree of the major scale of the key a note is spelled as,
+/// counting from `0`, along with the semi-tones it is altered from it.
+///
+/// In `C`, `Bb` is the seventh degree lowered by one, `(6, -1)`.
+fn scale_degree(note: &Note, key: Note) -> (usize, i32) {
+    let degree = key.letter_distance(note).rem_euclid(7) as usize;
+    let scale_note = key_scale(key, false).notes()[degree];
+    let alteration = (i32::from(note.pitch_class())
+                      - i32::from(scale_note.pitch_class()) + 6).rem_euclid(12) - 6;
+
+    (degree, alteration)
+}
+
+/// Returns the roman numeral of a chord in the key, such as `ii7` or `bVII`.
+///
+/// The degree is found from the letter name of the root relative to the major
+/// scale, with accidentals written before the numeral where the root is
+/// altered from the scale. The numeral is lowercase for chords with a minor
+/// third, and is followed by `°` for diminished, `ø` for half-diminished and
+/// `+` for augmented chords. Any seventh is written as `7`, or `Maj7` for a
+/// major seventh. Slash roots and other extensions are ignored.
+pub fn roman_numeral(chord: &Chord, key: Note) -> String {
+    const NUMERALS: [&str; 7] = ["I", "II", "III", "IV", "V", "VI", "VII"];
+
+    let (degree, alteration) = scale_degree(&chord.root, key);
+
+    let mut numeral = String::new();
+    for _ in 0..alteration.abs() {
+        numeral.push(if alteration < 0 { 'b' } else { '#' });
+    }
+
+    let third = chord.structure.get(PitchClass::N3);
+    let fifth = chord.structure.get(PitchClass::N5);
+    let seventh = chord.structure.get(PitchClass::N7);
+
+    if third == Some(-1) {
+        numeral.push_str(&NUMERALS[degree].to_lowercase());
+    } else {
+        numeral.push_str(NUMERALS[degree]);
+    }
+
+    numeral.push_str(match (third, fifth, seventh) {
+        (Some(-1), Some(-1), Some(0)) => "ø7",
+        (Some(-1), Some(-1), Some(-1)) => "°7",
+        (Some(-1), Some(-1), _) => "°",
+        (Some(0), Some(1), _) => "+",
+        _ => "",
+    });
+
+    numeral.push_str(match (third, fifth, seventh) {
+        (Some(-1), Some(-1), Some(0)) | (Some(-1), Some(-1), Some(-1)) => "",
+        (_, _, Some(1)) => "Maj7",
+        (_, _, Some(_)) => "7",
+        _ => "",
+    });
+
+    numeral
+}
+
+/// Returns the movable-do solfège syllable of each note of a chord in the
+/// key, in the order given by `Chord::iter`.
+///
+/// The syllables of the major scale are `Do Re Mi Fa Sol La Ti`. Notes raised
+/// from the scale are named `Di Ri Fi Si Li` and notes lowered from it
+/// `Ra Me Se Le Te`, so `E7` in `C` is `Mi Si Ti Re`. A raised or lowered note
+/// which falls on another degree of the scale, such as `Fb`, is named after
+/// that degree.
+pub fn to_solfege(chord: &Chord, key: Note) -> Vec<String> {
+    const RAISED: [&str; 12] = [
+        "Do", "Di", "Re", "Ri", "Mi", "Fa", "Fi", "Sol", "Si", "La", "Li", "Ti",
+    ];
+    const LOWERED: [&str; 12] = [
+        "Do", "Ra", "Re", "Me", "Mi", "Fa", "Se", "Sol", "Le", "La", "Te", "Ti",
+    ];
+
+    chord.iter()
+        .map(|note| {
+            let interval = (note.pitch_class() + 12 - key.pitch_class()) % 12;
+            let syllables = if scale_degree(&note, key).1 > 0 { RAISED } else { LOWERED };
+
+            syllables[usize::from(interval)].to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chord::NoteClass::*;
+
+    #[test]
+    fn dominant_and_subdominant() {
+        assert_eq!(dominant(Note::new(C, 0)), Note::new(G, 0));
+        assert_eq!(subdominant(Note::new(C, 0)), Note::new(F, 0));
+        assert_eq!(dominant(Note::new(F, 0)), Note::new(C, 0));
+        assert_eq!(subdominant(Note::new(F, 0)), Note::new(B, -1));
+        assert_eq!(dominant(Note::new(B, 0)), Note::new(F, 1));
+    }
+
+    #[test]
+    fn chord_in_key() {
+        let chord = parse_chord_in_key("C/bVII", Note::new(C, 0)).unwrap();
+        assert_eq!(chord, Chord::from_shorthand("C/Bb").unwrap());
+
+        let chord = parse_chord_in_key("G7/V", Note::new(G, 0)).unwrap();
+        assert_eq!(chord, Chord::from_shorthand("G7/D").unwrap());
+
+        assert!(parse_chord_in_key("C/bVIII", Note::new(C, 0)).is_err());
+    }
+
+    #[test]
+    fn secondary_dominants() {
+        let dm = Chord::from_shorthand("Dm").unwrap();
+        assert_eq!(secondary_dominant(&dm), Chord::from_shorthand("A7").unwrap());
+
+        let g = Chord::from_shorthand("G").unwrap();
+        assert_eq!(secondary_dominant(&g), Chord::from_shorthand("D7").unwrap());
+    }
+
+    #[test]
+    fn secondary_leading_tones() {
+        let dm = Chord::from_shorthand("Dm").unwrap();
+        assert_eq!(secondary_leading_tone(&dm), Chord::from_shorthand("C#dim7").unwrap());
+
+        let g = Chord::from_shorthand("G").unwrap();
+        assert_eq!(secondary_leading_tone(&g), Chord::from_shorthand("F#°7").unwrap());
+
+        let bb = Chord::from_shorthand("Bb").unwrap();
+        assert_eq!(secondary_leading_tone(&bb).root, Note::new(A, 0));
+    }
+
+    #[test]
+    fn modal_interchange_in_major() {
+        let borrowed = modal_interchange(Note::new(C, 0));
+
+        for symbol in &["Bb", "Fm", "Ab"] {
+            assert!(borrowed.contains(&Chord::from_shorthand(symbol).unwrap()));
+        }
+
+        assert_eq!(borrowed[0], Chord::from_shorthand("Fm").unwrap());
+        assert_eq!(modal_interchange(Note::new(D, 0))[1], Chord::from_shorthand("Bb").unwrap());
+    }
+
+    #[test]
+    fn key_signatures() {
+        assert_eq!(key_signature(Note::new(C, 0), false), 0);
+        assert_eq!(key_signature(Note::new(G, 0), false), 1);
+        assert_eq!(key_signature(Note::new(D, 0), false), 2);
+        assert_eq!(key_signature(Note::new(F, 0), false), -1);
+        assert_eq!(key_signature(Note::new(B, -1), false), -2);
+        assert_eq!(key_signature(Note::new(C, 1), false), 7);
+        assert_eq!(key_signature(Note::new(C, -1), false), -7);
+
+        assert_eq!(key_signature(Note::new(A, 0), true), 0);
+        assert_eq!(key_signature(Note::new(E, 0), true), 1);
+        assert_eq!(key_signature(Note::new(C, 0), true), -3);
+    }
+
+    #[test]
+    fn key_accidental_notes() {
+        assert_eq!(accidental_notes(Note::new(G, 0), false), vec![Note::new(F, 1)]);
+        assert_eq!(accidental_notes(Note::new(E, -1), false),
+                   vec![Note::new(B, -1), Note::new(E, -1), Note::new(A, -1)]);
+        assert_eq!(accidental_notes(Note::new(B, 0), true),
+                   vec![Note::new(F, 1), Note::new(C, 1)]);
+        assert!(accidental_notes(Note::new(C, 0), false).is_empty());
+    }
+
+    #[test]
+    fn harmonic_functions() {
+        let key = Note::new(C, 0);
+        let chord = |symbol| Chord::from_shorthand(symbol).unwrap();
+
+        assert!(same_function(&chord("Em"), &chord("C"), key));
+        assert!(same_function(&chord("Dm7"), &chord("F"), key));
+        assert!(same_function(&chord("G7"), &chord("Bdim"), key));
+        assert!(!same_function(&chord("G"), &chord("C"), key));
+        assert!(!same_function(&chord("Eb"), &chord("Eb"), key));
+
+        assert_eq!(harmonic_function(&chord("Am"), key), Some(HarmonicFunction::Tonic));
+        assert_eq!(harmonic_function(&chord("Em"), key), Some(HarmonicFunction::Tonic));
+        assert_eq!(harmonic_function(&chord("C"), key), Some(HarmonicFunction::Tonic));
+        assert_eq!(harmonic_function(&chord("G7"), key), Some(HarmonicFunction::Dominant));
+        assert_eq!(harmonic_function(&chord("Eb"), key), None);
+    }
+
+    #[test]
+    fn roman_numerals() {
+        let key = Note::new(C, 0);
+        let numeral = |symbol| roman_numeral(&Chord::from_shorthand(symbol).unwrap(), key);
+
+        assert_eq!(numeral("C"), "I");
+        assert_eq!(numeral("Dm7"), "ii7");
+        assert_eq!(numeral("G7"), "V7");
+        assert_eq!(numeral("CMaj7"), "IMaj7");
+        assert_eq!(numeral("Bm7(b5)"), "viiø7");
+        assert_eq!(numeral("Bdim"), "vii°");
+        assert_eq!(numeral("Bb"), "bVII");
+        assert_eq!(numeral("F#dim7"), "#iv°7");
+        assert_eq!(numeral("Eaug"), "III+");
+    }
+
+    #[test]
+    fn solfege() {
+        let key = Note::new(C, 0);
+        let solfege = |symbol| to_solfege(&Chord::from_shorthand(symbol).unwrap(), key);
+
+        assert_eq!(solfege("C"), vec!["Do", "Mi", "Sol"]);
+        assert_eq!(solfege("E7"), vec!["Mi", "Si", "Ti", "Re"]);
+        assert_eq!(solfege("Bb"), vec!["Te", "Re", "Fa"]);
+        assert_eq!(solfege("Db"), vec!["Ra", "Fa", "Le"]);
+        assert_eq!(to_solfege(&Chord::from_shorthand("D").unwrap(), Note::new(G, 0)),
+                   vec!["Sol", "Ti", "Re"]);
+    }
+
+    #[test]
+    fn pivots_between_keys() {
+        let chord = |symbol| Chord::from_shorthand(symbol).unwrap();
+        let pivots = pivot_chords(Note::new(C, 0), Note::new(G, 0));
+
+        assert_eq!(pivots, vec![chord("C"), chord("Em"), chord("G"), chord("Am")]);
+        assert!(!pivots.contains(&chord("F")));
+        assert_eq!(pivot_chords(Note::new(C, 0), Note::new(F, 1)), vec![]);
+    }
+}
@@ -5,9 +5,34 @@
 //! The `Chord` module contains a performant representation of tertian chords
 //! and provides functions for constructing and determining their constituent
 //! notes.
+//!
+//! The `progression` module provides functions which operate over sequences
+//! of chords, while the `analysis` module contains tools for examining the
+//! relationships between chords. Functions relating chords to a key are found
+//! in the `key` module and those for rendering them in written notation are in
+//! the `notation` module. Scales, and the scales suited to a given chord, are
+//! provided by the `scale` module, and the intervals between notes by the
+//! `interval` module.
 
 #[macro_use]
 extern crate combine;
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(test)]
+extern crate proptest;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
 
+pub mod analysis;
 pub mod chord;
+pub mod interval;
+pub mod key;
+pub mod midi;
+pub mod notation;
+pub mod progression;
+pub mod scale;
+#[cfg(feature = "serde")]
+mod json;
 mod parser;
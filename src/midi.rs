@@ -0,0 +1,149 @@
+//! Minimal writing of Standard MIDI Files, along with conversion from MIDI
+//! note numbers.
+//!
+//! Only what is required to render a single chord is supported, which keeps
+//! this free from any external dependencies.
+
+use chord::{Note, SpellingPreference};
+
+/// The number of ticks per quarter note used in written files.
+pub const TICKS_PER_QUARTER_NOTE: u16 = 480;
+
+/// The longest delta time which can be written, being the largest value of a
+/// four byte variable-length quantity.
+pub const MAX_DELTA_TICKS: u32 = 0x0fff_ffff;
+
+/// The velocity used for all note events.
+const VELOCITY: u8 = 64;
+
+/// Append a variable-length quantity as used for MIDI delta times.
+///
+/// Values must be no more than `MAX_DELTA_TICKS`.
+fn write_vlq(out: &mut Vec<u8>, value: u32) {
+    let mut shift = 28;
+    while shift > 0 && (value >> shift) & 0x7f == 0 {
+        shift -= 7;
+    }
+
+    while shift > 0 {
+        out.push(0x80 | ((value >> shift) & 0x7f) as u8);
+        shift -= 7;
+    }
+
+    out.push((value & 0x7f) as u8);
+}
+
+/// Returns the bytes of a format 0 Standard MIDI File which sounds all of
+/// the given notes together for `duration_ticks`.
+///
+/// All events are written on the first channel. Delta times are limited to
+/// four bytes, so a `duration_ticks` above `MAX_DELTA_TICKS` is clamped to
+/// it. If there are no notes the file holds only the end of track event,
+/// which still follows the duration.
+pub fn write_smf(notes: &[u8], duration_ticks: u32) -> Vec<u8> {
+    let duration_ticks = duration_ticks.min(MAX_DELTA_TICKS);
+    let mut track = Vec::new();
+
+    for &note in notes {
+        write_vlq(&mut track, 0);
+        track.extend_from_slice(&[0x90, note & 0x7f, VELOCITY]);
+    }
+
+    for (i, &note) in notes.iter().enumerate() {
+        write_vlq(&mut track, if i == 0 { duration_ticks } else { 0 });
+        track.extend_from_slice(&[0x80, note & 0x7f, VELOCITY]);
+    }
+
+    // End of track meta event
+    write_vlq(&mut track, if notes.is_empty() { duration_ticks } else { 0 });
+    track.extend_from_slice(&[0xff, 0x2f, 0x00]);
+
+    let mut out = Vec::with_capacity(22 + track.len());
+    out.extend_from_slice(b"MThd");
+    out.extend_from_slice(&[0, 0, 0, 6]);
+    out.extend_from_slice(&[0, 0]);
+    out.extend_from_slice(&[0, 1]);
+    out.extend_from_slice(&[(TICKS_PER_QUARTER_NOTE >> 8) as u8, TICKS_PER_QUARTER_NOTE as u8]);
+
+    out.extend_from_slice(b"MTrk");
+    let len = track.len() as u32;
+    out.extend_from_slice(&[(len >> 24) as u8, (len >> 16) as u8, (len >> 8) as u8, len as u8]);
+    out.extend_from_slice(&track);
+    out
+}
+
+/// Returns the note sounding at the given MIDI note number, spelled with
+/// sharps where an accidental is needed.
+///
+/// The octave is discarded, so both `60` and `72` give `C`.
+pub fn note_from_midi(midi: u8) -> Note {
+    note_from_midi_with(midi, SpellingPreference::Sharps)
+}
+
+/// Returns the note sounding at the given MIDI note number, spelled as per
+/// `preference` where an accidental is needed.
+pub fn note_from_midi_with(midi: u8, preference: SpellingPreference) -> Note {
+    Note::from_pitch_class(midi % 12, preference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vlq_encoding() {
+        let cases: &[(u32, &[u8])] = &[
+            (0, &[0x00]),
+            (0x7f, &[0x7f]),
+            (0x80, &[0x81, 0x00]),
+            (480, &[0x83, 0x60]),
+            (0x0fff_ffff, &[0xff, 0xff, 0xff, 0x7f]),
+        ];
+
+        for &(value, expected) in cases {
+            let mut out = Vec::new();
+            write_vlq(&mut out, value);
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn smf_layout() {
+        let bytes = write_smf(&[60, 64], 480);
+
+        let expected = vec![
+            b'M', b'T', b'h', b'd', 0, 0, 0, 6, 0, 0, 0, 1, 0x01, 0xe0,
+            b'M', b'T', b'r', b'k', 0, 0, 0, 21,
+            0x00, 0x90, 60, 64,
+            0x00, 0x90, 64, 64,
+            0x83, 0x60, 0x80, 60, 64,
+            0x00, 0x80, 64, 64,
+            0x00, 0xff, 0x2f, 0x00,
+        ];
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn smf_duration_limits() {
+        let track = |bytes: Vec<u8>| bytes[22..].to_vec();
+
+        let longest = [0xff, 0xff, 0xff, 0x7f, 0xff, 0x2f, 0x00];
+        assert_eq!(track(write_smf(&[], MAX_DELTA_TICKS)), longest);
+        assert_eq!(track(write_smf(&[], MAX_DELTA_TICKS + 1)), longest);
+        assert_eq!(track(write_smf(&[], u32::MAX)), longest);
+
+        assert_eq!(track(write_smf(&[], 480)), [0x83, 0x60, 0xff, 0x2f, 0x00]);
+        assert_eq!(write_smf(&[], 480)[18..22], [0, 0, 0, 5]);
+    }
+
+    #[test]
+    fn midi_to_note() {
+        use chord::NoteClass::*;
+
+        assert_eq!(note_from_midi(60), Note::new(C, 0));
+        assert_eq!(note_from_midi(61), Note::new(C, 1));
+        assert_eq!(note_from_midi(71), Note::new(B, 0));
+        assert_eq!(note_from_midi_with(61, SpellingPreference::Flats), Note::new(D, -1));
+    }
+}
@@ -0,0 +1,165 @@
+//! Functions for rendering notes and chords in written notation.
+
+use chord::*;
+
+use combine::Parser;
+
+/// A clef, which fixes the pitches of the lines of a five line staff.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Clef {
+    Treble, Alto, Tenor, Bass
+}
+
+impl Clef {
+    /// Returns the note and octave written on the bottom line of the staff.
+    fn bottom_line(&self) -> (Note, i8) {
+        use self::Clef::*;
+        use chord::NoteClass::*;
+
+        match *self {
+            Treble => (Note::new(E, 0), 4),
+            Alto => (Note::new(F, 0), 3),
+            Tenor => (Note::new(D, 0), 3),
+            Bass => (Note::new(G, 0), 2),
+        }
+    }
+}
+
+/// Returns the number of diatonic steps a note in the given octave lies above
+/// `C0`, ignoring accidentals.
+fn diatonic_steps(note: &Note, octave: i8) -> i32 {
+    i32::from(octave) * 7 + Note::new(NoteClass::C, 0).letter_distance(note)
+}
+
+/// Returns the position on the staff of each note of a chord as voiced by
+/// `Chord::voicing` with its root in `octave`.
+///
+/// Positions count lines and spaces upwards from the bottom line of the
+/// staff, which is `0`, so the lines are the even positions `0` to `8` and
+/// the spaces between them are odd. Notes outside of the staff have negative
+/// positions or positions above `8`, so middle C is `-2` in the treble clef.
+/// Accidentals don't affect the position.
+pub fn staff_positions(chord: &Chord, clef: Clef, octave: i8) -> Vec<i32> {
+    let (line, line_octave) = clef.bottom_line();
+    let bottom = diatonic_steps(&line, line_octave);
+
+    chord.voicing(octave)
+        .iter()
+        .map(|&(note, octave)| diatonic_steps(&note, octave) - bottom)
+        .collect()
+}
+
+/// Returns the figured bass numerals for a chord.
+///
+/// The figures are the intervals above the bass note (the slash root, or the
+/// root if there is none) using the standard abbreviations. A root position
+/// triad has no figures, a first inversion is `6` and a second inversion is
+/// `6/4`. Seventh chords in increasing inversion are `7`, `6/5`, `4/3` and
+/// `4/2`. Any other combination is listed in full from highest to lowest.
+pub fn figured_bass(chord: &Chord) -> String {
+    let bass = chord.slash_root.unwrap_or(chord.root);
+
+    let mut figures: Vec<usize> = chord.iter()
+        .map(|note| (note.root.to_int() + NOTE_CLASS_COUNT - bass.root.to_int())
+                    % NOTE_CLASS_COUNT + 1)
+        .filter(|&figure| figure != 1)
+        .collect();
+
+    figures.sort();
+    figures.dedup();
+
+    let abbreviated = match figures[..] {
+        [3, 5] => "",
+        [3, 6] => "6",
+        [4, 6] => "6/4",
+        [3, 5, 7] => "7",
+        [3, 5, 6] => "6/5",
+        [3, 4, 6] => "4/3",
+        [2, 4, 6] => "4/2",
+        _ => {
+            let full: Vec<_> = figures.iter().rev().map(|f| f.to_string()).collect();
+            return full.join("/");
+        }
+    };
+
+    abbreviated.to_string()
+}
+
+/// Returns a note in scientific pitch notation, such as `C#4` or `Bb3`.
+///
+/// Middle C is `C4`, and the octave number changes between `B` and `C`.
+pub fn format_spn(note: &Note, octave: i8) -> String {
+    format!("{}{}", note, octave)
+}
+
+/// Parse a note in scientific pitch notation, such as `C#4` or `Ebb2`, into
+/// the note and its octave.
+///
+/// The note is read in the same way as the root of a chord.
+pub fn parse_spn(input: &str) -> ParseResult<'_, (Note, i8)> {
+    ::parser::parse_spn().parse(input).map(|c| c.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn figures(symbol: &str) -> String {
+        figured_bass(&Chord::from_shorthand(symbol).unwrap())
+    }
+
+    #[test]
+    fn figured_bass_triads() {
+        assert_eq!(figures("C"), "");
+        assert_eq!(figures("C/E"), "6");
+        assert_eq!(figures("C/G"), "6/4");
+    }
+
+    #[test]
+    fn figured_bass_sevenths() {
+        assert_eq!(figures("G7"), "7");
+        assert_eq!(figures("G7/B"), "6/5");
+        assert_eq!(figures("G7/D"), "4/3");
+        assert_eq!(figures("G7/F"), "4/2");
+    }
+
+    #[test]
+    fn figured_bass_other() {
+        assert_eq!(figures("C/D"), "7/4/2");
+        assert_eq!(figures("C5"), "5");
+    }
+
+    #[test]
+    fn spn_formatting() {
+        use chord::NoteClass::*;
+
+        assert_eq!(format_spn(&Note::new(C, 0), 4), "C4");
+        assert_eq!(format_spn(&Note::new(A, 0), 4), "A4");
+        assert_eq!(format_spn(&Note::new(C, 1), 4), "C#4");
+        assert_eq!(format_spn(&Note::new(B, -1), 3), "Bb3");
+        assert_eq!(format_spn(&Note::new(C, 0), -1), "C-1");
+    }
+
+    #[test]
+    fn spn_parsing() {
+        use chord::NoteClass::*;
+
+        assert_eq!(parse_spn("A4"), Ok((Note::new(A, 0), 4)));
+        assert_eq!(parse_spn("Bbb3"), Ok((Note::new(B, -2), 3)));
+        assert_eq!(parse_spn(&format_spn(&Note::new(F, 1), 5)), Ok((Note::new(F, 1), 5)));
+        assert!(parse_spn("A4 ").is_err());
+    }
+
+    #[test]
+    fn staff_positions_in_clefs() {
+        let c = Chord::from_shorthand("C").unwrap();
+
+        assert_eq!(staff_positions(&c, Clef::Treble, 4), vec![-2, 0, 2]);
+        assert_eq!(staff_positions(&c, Clef::Treble, 5), vec![5, 7, 9]);
+        assert_eq!(staff_positions(&c, Clef::Bass, 4)[0], 10);
+        assert_eq!(staff_positions(&c, Clef::Alto, 4)[0], 4);
+
+        let c_sharp = Chord::from_shorthand("C#").unwrap();
+        assert_eq!(staff_positions(&c_sharp, Clef::Treble, 4), vec![-2, 0, 2]);
+    }
+}
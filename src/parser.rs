@@ -1,13 +1,17 @@
 //! Handles parsing of various free-form inputs.
 
 #![allow(dead_code)]
-#![cfg_attr(feature="cargo-clippy", allow(unneeded_field_pattern))]
+#![allow(clippy::unneeded_field_pattern)]
+
+use std::iter;
 
 use chord::*;
 
 use combine::{Stream, ParseResult, Parser};
-use combine::{eof, between, choice, parser, many, one_of, optional, token, try, chainl1};
-use combine::char::{string, spaces};
+use combine::{eof, between, choice, parser, many, many1, one_of, optional, token, try, chainl1};
+use combine::{env_parser, not_followed_by, sep_by1};
+use combine::char::{digit, string, string_cmp, spaces};
+use combine::primitives::{Error, Info};
 
 parser! {
     pub fn parse_chord[I]()(I) -> Chord
@@ -17,6 +21,38 @@ parser! {
     }
 }
 
+parser! {
+    pub fn parse_chord_prefix[I]()(I) -> Chord
+        where [I: Stream<Item=char>]
+    {
+        parser(chord)
+    }
+}
+
+parser! {
+    pub fn parse_chord_in_key[I](key: Note)(I) -> Chord
+        where [I: Stream<Item=char>]
+    {
+        env_parser(*key, chord_in_key).skip(eof())
+    }
+}
+
+parser! {
+    pub fn parse_chord_lowercase_minor[I]()(I) -> Chord
+        where [I: Stream<Item=char>]
+    {
+        parser(lowercase_chord).or(parser(chord)).skip(eof())
+    }
+}
+
+parser! {
+    pub fn parse_spn[I]()(I) -> (Note, i8)
+        where [I: Stream<Item=char>]
+    {
+        parser(scientific_pitch).skip(eof())
+    }
+}
+
 parser! {
     pub fn parse_polychord[I]()(I) -> PolyChord
         where [I: Stream<Item=char>]
@@ -25,6 +61,15 @@ parser! {
     }
 }
 
+/// Parses a keyword, ignoring the case of its letters.
+///
+/// The output is always `word` as given, whatever the case of the input.
+fn keyword<I>(word: &'static str) -> impl Parser<Input=I, Output=&'static str>
+    where I: Stream<Item=char>
+{
+    try(string_cmp(word, |l: char, r: char| l.eq_ignore_ascii_case(&r)))
+}
+
 /// Parses a single accidental.
 ///
 /// ```text
@@ -75,6 +120,74 @@ fn note<I>(input: I) -> ParseResult<Note, I>
         .parse_stream(input)
 }
 
+/// Parses a signed integer which fits within an `i8`.
+///
+/// ```text
+/// Integer : '-'? [0-9]+
+///         ;
+/// ```
+fn integer<I>(input: I) -> ParseResult<i8, I>
+    where I: Stream<Item=char>
+{
+    (optional(token('-')), many1(digit()))
+        .and_then(|(sign, digits): (Option<char>, String)| match sign {
+            Some(_) => format!("-{}", digits).parse::<i8>(),
+            None => digits.parse::<i8>(),
+        })
+        .parse_stream(input)
+}
+
+/// Parses a note in scientific pitch notation, such as `C#4`.
+///
+/// ```text
+/// ScientificPitch : Note Integer
+///                 ;
+/// ```
+fn scientific_pitch<I>(input: I) -> ParseResult<(Note, i8), I>
+    where I: Stream<Item=char>
+{
+    (parser(note), parser(integer)).parse_stream(input)
+}
+
+/// Parses a scale degree written as a roman numeral.
+///
+/// The degree is relative to the major scale, so `VII` is a major seventh and
+/// `bVII` a minor seventh above the tonic.
+///
+/// ```text
+/// Degree : Accidental* ('VII' | 'VI' | 'V' | 'IV' | 'III' | 'II' | 'I')
+///        ;
+/// ```
+fn degree<I>(input: I) -> ParseResult<ChordComponent, I>
+    where I: Stream<Item=char>
+{
+    let offset =
+        many(parser(accidental))
+            .map(|x: Vec<PitchOffset>| x.iter().sum::<PitchOffset>());
+
+    let numeral =
+        choice([
+            try(string("VII")), try(string("VI")), try(string("V")),
+            try(string("IV")), try(string("III")), try(string("II")),
+            try(string("I"))
+        ])
+        .map(|q| match q {
+            "I"   => (PitchClass::N1, 0),
+            "II"  => (PitchClass::N2, 0),
+            "III" => (PitchClass::N3, 0),
+            "IV"  => (PitchClass::N4, 0),
+            "V"   => (PitchClass::N5, 0),
+            "VI"  => (PitchClass::N6, 0),
+            "VII" => (PitchClass::N7, 1),
+            _ => unreachable!()
+        })
+        .expected("Degree: [I-VII]");
+
+    (offset, numeral)
+        .map(|(offset, (class, natural))| (class, natural + offset))
+        .parse_stream(input)
+}
+
 /// Parses a standard chord using the default rules.
 ///
 /// This will recognize standard chords based on a third/seventh interval with
@@ -86,23 +199,51 @@ fn note<I>(input: I) -> ParseResult<Note, I>
 /// ThirdQuality : 'min' | 'mi' | 'm' | '-'
 ///              ;
 ///
-/// SeventhQuality : 'Maj' | 'Ma' | 'M' | 'Δ'
+/// SeventhQuality : 'maj' | 'Ma' | 'ma' | 'M' | 'Δ'
 ///                ;
 ///
 /// ExtendedInterval : '7' | '9' | '11' | '13'
 ///                  ;
 ///
-/// ChordStandard : ThirdQuality? (SeventhQuality ExtendedInterval)?
+/// Sixth : '6/9' | '6'
+///       ;
+///
+/// Extended : Sixth | SeventhQuality? ExtendedInterval | 'Δ'
+///          ;
+///
+/// ChordStandard : ThirdQuality? Extended?
 ///               ;
 /// ```
+///
+/// The `min` and `maj` keywords are matched regardless of case, so `CMAJ7`
+/// and `Cmaj7` are the same chord. The shorter forms are case-sensitive as
+/// `m` and `M` differ in meaning. The `sus` and `add` keywords which may
+/// follow a standard chord, as in `C7sus4` and `Cadd9`, are likewise matched
+/// regardless of case (see `suspension` and `added_tone`).
+///
+/// A `-` is only ever read as a minor third directly following the root, so
+/// `C-7` is `Cm7` and `C-Δ7` is `CmMaj7`.
+///
+/// A lone `Δ` is treated as shorthand for a major seventh (i.e. `CΔ` is the
+/// same as `CΔ7`). Since `m` is a prefix of `maj` and `ma`, the minor third
+/// quality `m` is only recognized when not directly followed by an `a`, unless
+/// that begins an `add` as in `Cmadd9`.
+///
+/// The `6/9` chord is matched before any slash extension is considered, so
+/// `C6/9` is a six-nine chord while `C6/E` is a `C6` with an `E` bass.
 fn chord_standard<I>(input: I) -> ParseResult<ChordStructure, I>
     where I: Stream<Item=char>
 {
+    // The `m` of `maj` and `ma` is a major seventh rather than a minor third,
+    // though that of `madd` is not.
+    let major_seventh = try(token('a').skip(not_followed_by(token('d'))));
+
     let third =
-        optional(choice([
-                try(string("min")), try(string("mi")),
-                try(string("m")), try(string("-")),
-            ]))
+        optional(choice!(
+                keyword("min"), try(string("mi")),
+                try(string("m").skip(not_followed_by(major_seventh))),
+                try(string("-"))
+            ))
             .map(|q| match q {
                 Some("min") | Some("mi") | Some("m") | Some("-") => {
                     ChordStructure::from_component((PitchClass::N3, -1))
@@ -118,12 +259,13 @@ fn chord_standard<I>(input: I) -> ParseResult<ChordStructure, I>
             });
 
     let seventh =
-        optional(choice([
-                try(string("Maj")), try(string("Ma")),
+        optional(choice!(
+                keyword("maj"),
+                try(string("Ma")), try(string("ma")),
                 try(string("M")), try(string("Δ"))
-            ]))
+            ))
             .map(|q| match q {
-                Some("Maj") | Some("Ma") | Some("M") | Some("Δ") => {
+                Some("maj") | Some("Ma") | Some("ma") | Some("M") | Some("Δ") => {
                     (PitchClass::N7, 1)
                 }
 
@@ -143,15 +285,35 @@ fn chord_standard<I>(input: I) -> ParseResult<ChordStructure, I>
             _ => unreachable!()
         }.extended_intervals());
 
-    let extended =
-        optional(seventh.and(interval))
+    let sixth =
+        choice([try(string("6/9")), try(string("6"))])
             .map(|q| match q {
-                Some((q, i)) => {
-                    ChordStructure::new()
-                        .insert_many(i)
-                        .insert(q)
+                "6/9" => {
+                    ChordStructure::from_component((PitchClass::N6, 0))
+                        .insert((PitchClass::N9, 0))
                 }
 
+                "6" => ChordStructure::from_component((PitchClass::N6, 0)),
+
+                _ => unreachable!()
+            });
+
+    let seventh_interval =
+        seventh.and(interval)
+            .map(|(q, i)| {
+                ChordStructure::new()
+                    .insert_many(i)
+                    .insert(q)
+            });
+
+    let delta =
+        token('Δ')
+            .map(|_| ChordStructure::new().insert((PitchClass::N7, 1)));
+
+    let extended =
+        optional(sixth.or(try(seventh_interval)).or(delta))
+            .map(|q| match q {
+                Some(extended) => extended,
                 None => ChordStructure::new()
             });
 
@@ -180,14 +342,16 @@ fn chord_standard<I>(input: I) -> ParseResult<ChordStructure, I>
 /// ChordAugDim : (Augmented | Diminished) ExtendedQuality?
 ///             ;
 /// ```
+///
+/// The `dim` and `aug` keywords are matched regardless of case.
 fn chord_aug_or_dim<I>(input: I) -> ParseResult<ChordStructure, I>
     where I: Stream<Item=char>
 {
     let mut aug_dim =
-        choice([
-            try(string("dim")), try(string("°")),
-            try(string("aug")), try(string("+"))
-        ])
+        choice!(
+            keyword("dim"), try(string("°")),
+            keyword("aug"), try(string("+"))
+        )
         .and(optional(token('7')))
         .map(|(q, e)| match q {
             "dim" | "°" => {
@@ -240,6 +404,119 @@ fn chord_special<I>(input: I) -> ParseResult<ChordStructure, I>
     chord_fifth.or(parser(chord_aug_or_dim)).parse_stream(input)
 }
 
+/// Parses a chord quality written out as a word, optionally followed by its
+/// extension.
+///
+/// An example of a chord this parser recognizes is `A minor 7`. Any spaces
+/// before the word and between the word and its extension are skipped, and
+/// the word is matched regardless of case. A dominant chord without an
+/// extension is taken to be a seventh.
+///
+/// ```text
+/// WordQuality : 'major' | 'minor' | 'dominant' | 'diminished' | 'augmented'
+///             ;
+///
+/// WordExtension : '7' | '9' | '11' | '13'
+///               ;
+///
+/// ChordWords : WordQuality WordExtension?
+///            ;
+/// ```
+fn chord_words<I>(input: I) -> ParseResult<ChordStructure, I>
+    where I: Stream<Item=char>
+{
+    let quality =
+        choice!(
+            keyword("major"), keyword("minor"), keyword("dominant"),
+            keyword("diminished"), keyword("augmented")
+        )
+        .map(|q| match q {
+            "major"      => ChordQuality::Major,
+            "minor"      => ChordQuality::Minor,
+            "dominant"   => ChordQuality::Dominant,
+            "diminished" => ChordQuality::Diminished,
+            "augmented"  => ChordQuality::Augmented,
+            _ => unreachable!()
+        });
+
+    let extension =
+        choice([
+            try(string("7")), try(string("9")),
+            try(string("11")), try(string("13"))
+        ])
+        .map(|e| match e {
+            "7"  => ChordExtent::Seventh,
+            "9"  => ChordExtent::Ninth,
+            "11" => ChordExtent::Eleventh,
+            "13" => ChordExtent::Thirteenth,
+            _ => unreachable!()
+        });
+
+    (spaces().with(quality), optional(try(spaces().with(extension))))
+        .map(|(quality, extension)| {
+            let extent = match (quality, extension) {
+                (_, Some(extent)) => extent,
+                (ChordQuality::Dominant, None) => ChordExtent::Seventh,
+                (_, None) => ChordExtent::Triad,
+            };
+
+            ChordStructure::from_quality(quality, extent)
+        })
+        .parse_stream(input)
+}
+
+/// Parses a suspension, which replaces the third of a chord with a second or
+/// fourth, such as the `sus4` of `C7sus4`.
+///
+/// ```text
+/// Suspension : 'sus' ('2' | '4')?
+///            ;
+/// ```
+///
+/// A `sus` on its own is a suspended fourth. The keyword is matched
+/// regardless of case.
+fn suspension<I>(input: I) -> ParseResult<PitchClass, I>
+    where I: Stream<Item=char>
+{
+    keyword("sus")
+        .with(optional(one_of("24".chars())))
+        .map(|q| match q {
+            Some('2') => PitchClass::N2,
+            Some('4') | None => PitchClass::N4,
+            _ => unreachable!()
+        })
+        .parse_stream(input)
+}
+
+/// Parses a tone added to a chord, such as the `add9` of `Cadd9`.
+///
+/// ```text
+/// AddedTone : 'add' ('2' | '4' | '6' | '9' | '11' | '13')
+///           ;
+/// ```
+///
+/// The keyword is matched regardless of case.
+fn added_tone<I>(input: I) -> ParseResult<PitchClass, I>
+    where I: Stream<Item=char>
+{
+    let added_interval =
+        choice([
+            try(string("2")), try(string("4")), try(string("6")),
+            try(string("9")), try(string("11")), try(string("13"))
+        ])
+        .map(|q| match q {
+            "2"  => PitchClass::N2,
+            "4"  => PitchClass::N4,
+            "6"  => PitchClass::N6,
+            "9"  => PitchClass::N9,
+            "11" => PitchClass::N11,
+            "13" => PitchClass::N13,
+            _ => unreachable!()
+        });
+
+    keyword("add").with(added_interval).parse_stream(input)
+}
+
 /// Parses a set of chord alterations that may appear at the end of a chord.
 ///
 /// An example of a set of alterations is final enclosed group in the
@@ -252,6 +529,10 @@ fn chord_special<I>(input: I) -> ParseResult<ChordStructure, I>
 /// Alterations : '(' (Alteration ',')* ')'
 ///             ;
 /// ```
+///
+/// A `ChordStructure` holds a single offset per interval, so if the same
+/// interval is altered more than once the last alteration wins. For example,
+/// `C7(b5,#5)` has only a sharpened fifth.
 fn chord_alterations<I>(input: I) -> ParseResult<ChordStructure, I>
     where I: Stream<Item=char>
 {
@@ -280,9 +561,9 @@ fn chord_alterations<I>(input: I) -> ParseResult<ChordStructure, I>
         (optional(spaces()), token(','), optional(spaces()))
             .map(|_| |l: ChordStructure, r: ChordStructure| l.merge(&r));
 
-    optional(between(token('(').and(optional(spaces())), token(')'),
+    optional(try(between(token('(').and(optional(spaces())), token(')'),
         chainl1(alteration, chain_op)
-    ))
+    )))
     .map(|q| match q {
         Some(inner) => inner,
         None => ChordStructure::new()
@@ -290,6 +571,73 @@ fn chord_alterations<I>(input: I) -> ParseResult<ChordStructure, I>
     .parse_stream(input)
 }
 
+/// Parses the chord tones which are omitted from a chord.
+///
+/// Any of the root, third and fifth may be omitted, such as the root of a
+/// rootless voicing, `CMaj7(omit1)`.
+///
+/// ```text
+/// Omission : '1' | '3' | '5'
+///          ;
+///
+/// Omissions : '(' 'omit' Omission (',' Omission)* ')'
+///           ;
+/// ```
+fn chord_omissions<I>(input: I) -> ParseResult<Vec<PitchClass>, I>
+    where I: Stream<Item=char>
+{
+    let omission =
+        one_of("135".chars())
+            .map(|c| match c {
+                '1' => PitchClass::N1,
+                '3' => PitchClass::N3,
+                '5' => PitchClass::N5,
+                _ => unreachable!()
+            });
+
+    let separator = (optional(spaces()), token(','), optional(spaces()));
+
+    between(
+        try((token('('), optional(spaces()), keyword("omit"))).and(optional(spaces())),
+        token(')'),
+        sep_by1(omission, try(separator))
+    )
+    .parse_stream(input)
+}
+
+/// Parse an added interval written with a slash.
+///
+/// Some notations write an added tone after a slash, such as `C/9` for an
+/// added ninth. A slash followed by an interval number is always an added
+/// tone, while a slash followed by a note is a `SlashExtension`.
+///
+/// ```text
+/// SlashAddition : '/' ('2' | '4' | '6' | '9' | '11' | '13')
+///               ;
+/// ```
+fn slash_addition<I>(input: I) -> ParseResult<ChordStructure, I>
+    where I: Stream<Item=char>
+{
+    let added_interval =
+        choice([
+            try(string("2")), try(string("4")), try(string("6")),
+            try(string("9")), try(string("11")), try(string("13"))
+        ])
+        .map(|q| match q {
+            "2"  => PitchClass::N2,
+            "4"  => PitchClass::N4,
+            "6"  => PitchClass::N6,
+            "9"  => PitchClass::N9,
+            "11" => PitchClass::N11,
+            "13" => PitchClass::N13,
+            _ => unreachable!()
+        });
+
+    (token('/'), added_interval)
+        .map(|(_, class)| ChordStructure::from_component((class, 0)))
+        .parse_stream(input)
+}
+
 /// Parse a trailing slash chord extension.
 ///
 /// And example of a slash extension this parses is `/A#`.
@@ -306,51 +654,173 @@ fn slash_extension<I>(input: I) -> ParseResult<Note, I>
         .parse_stream(input)
 }
 
-/// Recognizes an entire chord of any type.
+/// Recognizes the structure of a chord of any type following its root.
 ///
 /// ```text
-/// Chord : Note (ChordSpecial | ChordStandard) ChordAlterations SlashExtension?
-///       ;
+/// ChordSuffix : (ChordWords | ChordSpecial | ChordStandard)
+///               Suspension? AddedTone* ChordAlterations Omissions? SlashAddition?
+///             ;
 /// ```
-fn chord<I>(input: I) -> ParseResult<Chord, I>
+///
+/// If `implied_minor` is set then a standard chord with no quality written,
+/// such as `7` or `6`, is taken to be minor. Chords whose quality is given
+/// explicitly, whether as a word, `aug`, `dim` or a major seventh, are left
+/// as written.
+fn chord_suffix<I>(implied_minor: bool, input: I) -> ParseResult<ChordStructure, I>
     where I: Stream<Item=char>
 {
-    let chord = try(parser(chord_special)).or(try(parser(chord_standard)));
+    let standard =
+        parser(chord_standard)
+            .map(|structure: ChordStructure| {
+                // Only minor thirds and major sevenths are ever written, so a
+                // major third without a major seventh means no quality was.
+                let implicit = structure.get(PitchClass::N3) == Some(0)
+                    && structure.get(PitchClass::N7) != Some(1);
+
+                if implied_minor && implicit {
+                    structure.insert((PitchClass::N3, -1))
+                } else {
+                    structure
+                }
+            });
+
+    let chord =
+        try(parser(chord_words))
+            .or(try(parser(chord_special)))
+            .or(try(standard));
 
     (
-        parser(note),
         chord,
+        optional(parser(suspension)),
+        many::<Vec<_>, _>(parser(added_tone)),
         parser(chord_alterations),
-        optional(parser(slash_extension))
+        optional(parser(chord_omissions)),
+        optional(try(parser(slash_addition)))
     )
-    .map(|(root, standard, alterations, slash)| {
-        Chord {
-            slash_root: slash,
-            root: root,
-            structure: ChordStructure::new()
-                            .merge(&standard)
-                            .merge(&alterations)
-        }
+    .map(|(standard, suspension, added, alterations, omissions, addition)| {
+        let standard = match suspension {
+            Some(class) => standard.remove(PitchClass::N3).insert((class, 0)),
+            None => standard,
+        };
+
+        let added: Vec<_> = added.into_iter().map(|class| (class, 0)).collect();
+
+        let structure = ChordStructure::new()
+            .merge(&standard)
+            .insert_many(&added)
+            .merge(&alterations)
+            .merge(&addition.unwrap_or_default());
+
+        omissions.unwrap_or_default()
+            .into_iter()
+            .fold(structure, ChordStructure::remove)
     })
     .parse_stream(input)
 }
 
+/// Recognizes a chord of any type without a slash extension.
+///
+/// ```text
+/// ChordBody : Note ChordSuffix
+///           ;
+/// ```
+fn chord_body<I>(input: I) -> ParseResult<Chord, I>
+    where I: Stream<Item=char>
+{
+    (parser(note), env_parser(false, chord_suffix))
+        .map(|(root, structure)| Chord::new(root, structure))
+        .parse_stream(input)
+}
+
+/// Recognizes a chord whose root is written in lowercase, which marks it as
+/// minor.
+///
+/// ```text
+/// LowercaseChord : [a-g] Accidental* ChordSuffix SlashExtension?
+///                ;
+/// ```
+///
+/// A chord without a written quality is minor, so `c7` is `Cm7`. A quality
+/// which is written out is kept, so `caug`, `cmaj7` and `c major` are the
+/// same as `Caug`, `Cmaj7` and `C major`, as are chords without a third such
+/// as `c5`.
+fn lowercase_chord<I>(input: I) -> ParseResult<Chord, I>
+    where I: Stream<Item=char>
+{
+    let root_note =
+        one_of("abcdefg".chars())
+            .map(|c: char| NoteClass::from_char(c.to_ascii_uppercase()).unwrap())
+            .expected("Note: [a-g]");
+
+    let offset =
+        many(parser(accidental))
+            .map(|x: Vec<PitchOffset>| x.iter().sum());
+
+    (root_note, offset, env_parser(true, chord_suffix), optional(parser(slash_extension)))
+        .map(|(root, offset, structure, slash)| {
+            Chord { slash_root: slash, ..Chord::new(Note::new(root, offset), structure) }
+        })
+        .parse_stream(input)
+}
+
+/// Recognizes an entire chord of any type.
+///
+/// ```text
+/// Chord : ChordBody SlashExtension?
+///       ;
+/// ```
+fn chord<I>(input: I) -> ParseResult<Chord, I>
+    where I: Stream<Item=char>
+{
+    (parser(chord_body), optional(parser(slash_extension)))
+        .map(|(chord, slash)| Chord { slash_root: slash, ..chord })
+        .parse_stream(input)
+}
+
+/// Recognizes an entire chord whose slash extension may be given as a scale
+/// degree of `key`, such as `C/bVII`.
+///
+/// ```text
+/// ChordInKey : ChordBody ('/' (Note | Degree))?
+///            ;
+/// ```
+fn chord_in_key<I>(key: Note, input: I) -> ParseResult<Chord, I>
+    where I: Stream<Item=char>
+{
+    let bass =
+        parser(note)
+            .or(parser(degree).map(|d| key.get_relative(d)));
+
+    (parser(chord_body), optional(token('/').with(bass)))
+        .map(|(chord, slash)| Chord { slash_root: slash, ..chord })
+        .parse_stream(input)
+}
+
 /// Recognizes a polychord.
 ///
 /// ```text
-/// PolyChord : Chord '|' Chord
+/// PolyChord : Chord ('|' Chord)+
 ///           ;
 /// ```
+///
+/// Any number of layers may be stacked, with the first chord uppermost. The
+/// layers are kept flat rather than nested, so `A|B|C` is simply `A` above
+/// `B` above `C` and there is no grouping to choose between.
 fn polychord<I>(input: I) -> ParseResult<PolyChord, I>
     where I: Stream<Item=char>
 {
-    (
-        parser(chord).skip(spaces()),
-        token('|').skip(spaces()),
-        parser(chord)
-    )
-    .map(|(upper, _, lower)| PolyChord::new(upper, lower))
-    .parse_stream(input)
+    let layer =
+        try((spaces(), token('|')))
+            .skip(spaces())
+            .with(parser(chord));
+
+    (parser(chord), many1(layer))
+        .and_then(|(upper, lower): (Chord, Vec<Chord>)| {
+            PolyChord::from_layers(iter::once(upper).chain(lower).collect()).ok_or_else(|| {
+                Error::<char, I::Range>::Message(Info::Borrowed("Too few polychord layers"))
+            })
+        })
+        .parse_stream(input)
 }
 
 #[cfg(test)]
@@ -463,6 +933,20 @@ mod tests {
         assert_eq!(result, Ok((expected, "")));
     }
 
+    #[test]
+    fn parse_major_seventh_aliases() {
+        let expected = Chord::new(
+            Note::new(C, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, 0), (N5, 0), (N7, 1)])
+        );
+
+        for input in &["CΔ", "CΔ7", "Cmaj7", "CMaj7", "CM7", "Cma7", "CMa7"] {
+            let result = parser(chord).parse(*input);
+            assert_eq!(result, Ok((expected.clone(), "")), "input: {}", input);
+        }
+    }
+
     #[test]
     fn parse_minor_seventh_chord() {
         let result = parser(chord).parse("Cm7");
@@ -487,6 +971,52 @@ mod tests {
         assert_eq!(result, Ok((expected, "")));
     }
 
+    #[test]
+    fn parse_sixth_chords() {
+        let result = parser(chord).parse("C6");
+        let expected = Chord::new(
+            Note::new(C, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, 0), (N5, 0), (N6, 0)])
+        );
+
+        assert_eq!(result, Ok((expected, "")));
+
+        let result = parser(chord).parse("Cm6");
+        let expected = Chord::new(
+            Note::new(C, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, -1), (N5, 0), (N6, 0)])
+        );
+
+        assert_eq!(result, Ok((expected, "")));
+    }
+
+    #[test]
+    fn parse_six_nine_chord() {
+        let result = parser(chord).parse("C6/9");
+        let expected = Chord::new(
+            Note::new(C, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, 0), (N5, 0), (N6, 0), (N9, 0)])
+        );
+
+        assert_eq!(result, Ok((expected, "")));
+    }
+
+    #[test]
+    fn parse_sixth_slash_chord() {
+        let result = parser(chord).parse("C6/E");
+        let expected = Chord::new_slash(
+            Note::new(E, 0),
+            Note::new(C, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, 0), (N5, 0), (N6, 0)])
+        );
+
+        assert_eq!(result, Ok((expected, "")));
+    }
+
     #[test]
     fn parse_minor_major_seventh() {
         let result = parser(chord).parse("CmMaj7");
@@ -535,6 +1065,34 @@ mod tests {
         assert_eq!(result, Ok((expected, "")));
     }
 
+    #[test]
+    fn parse_omissions() {
+        let result = parser(chord).parse("Cm7(b5)(OMIT 1, 5)/E");
+        let expected = Chord::new_slash(
+            Note::new(E, 0),
+            Note::new(C, 0),
+            ChordStructure::default()
+                .insert_many(&[(N3, -1), (N7, 0)])
+        );
+
+        assert_eq!(result, Ok((expected, "")));
+
+        let result = parser(chord).parse("C(omit)");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_alterations_repeated_interval() {
+        let result = parser(chord).parse("C7(b5,#5)");
+        let expected = Chord::new(
+            Note::new(C, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, 0), (N5, 1), (N7, 0)])
+        );
+
+        assert_eq!(result, Ok((expected, "")));
+    }
+
     #[test]
     fn parse_augmented_triad() {
         let result = parser(chord).parse("C+");
@@ -630,6 +1188,72 @@ mod tests {
         assert_eq!(result, Ok((expected, "")));
     }
 
+    #[test]
+    fn parse_slash_addition() {
+        let result = parser(chord).parse("C/9");
+        let expected = Chord::new(
+            Note::new(C, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, 0), (N5, 0), (N9, 0)]),
+        );
+
+        assert_eq!(result, Ok((expected, "")));
+    }
+
+    #[test]
+    fn parse_slash_addition_with_bass() {
+        let result = parser(chord).parse("Am/9/C");
+        let expected = Chord::new_slash(
+            Note::new(C, 0),
+            Note::new(A, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, -1), (N5, 0), (N9, 0)]),
+        );
+
+        assert_eq!(result, Ok((expected, "")));
+    }
+
+    #[test]
+    fn parse_degree() {
+        assert_eq!(parser(degree).parse("V"), Ok(((N5, 0), "")));
+        assert_eq!(parser(degree).parse("bVII"), Ok(((N7, 0), "")));
+        assert_eq!(parser(degree).parse("#IV"), Ok(((N4, 1), "")));
+        assert_eq!(parser(degree).parse("bbIII"), Ok(((N3, -2), "")));
+    }
+
+    #[test]
+    fn parse_chord_degree_bass() {
+        let result = env_parser(Note::new(C, 0), chord_in_key).parse("C/bVII");
+        let expected = Chord::new_slash(
+            Note::new(B, -1),
+            Note::new(C, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, 0), (N5, 0)]),
+        );
+
+        assert_eq!(result, Ok((expected, "")));
+
+        let result = env_parser(Note::new(E, -1), chord_in_key).parse("Fm7/V");
+        let expected = Chord::new_slash(
+            Note::new(B, -1),
+            Note::new(F, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, -1), (N5, 0), (N7, 0)]),
+        );
+
+        assert_eq!(result, Ok((expected, "")));
+
+        let result = env_parser(Note::new(E, -1), chord_in_key).parse("C/E");
+        let expected = Chord::new_slash(
+            Note::new(E, 0),
+            Note::new(C, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, 0), (N5, 0)]),
+        );
+
+        assert_eq!(result, Ok((expected, "")));
+    }
+
     #[test]
     fn parse_simple_polychord() {
         let result = parser(polychord).parse("F|Cb");
@@ -672,6 +1296,27 @@ mod tests {
         assert_eq!(result, Ok((expected, "")));
     }
 
+    #[test]
+    fn parse_altered_polychord() {
+        let result = parser(polychord).parse("F#maj7(#11)|Cm9");
+
+        let upper = Chord::new(
+            Note::new(F, 1),
+            ChordStructure::new()
+                .insert_many(&[(N3, 0), (N5, 0), (N7, 1), (N11, 1)])
+        );
+
+        let lower = Chord::new(
+            Note::new(C, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, -1), (N5, 0), (N7, 0), (N9, 0)])
+        );
+
+        let expected = PolyChord::new(upper, lower);
+
+        assert_eq!(result, Ok((expected, "")));
+    }
+
     #[test]
     fn parse_slash_polychord() {
         let result = parser(polychord).parse("A/C#|Gm/Bb");
@@ -694,4 +1339,119 @@ mod tests {
 
         assert_eq!(result, Ok((expected, "")));
     }
+
+    #[test]
+    fn parse_layered_polychord() {
+        let result = parser(polychord).parse("D | C|G7");
+
+        let expected = PolyChord::from_layers(vec![
+            Chord::new(Note::new(D, 0), ChordStructure::major_triad()),
+            Chord::new(Note::new(C, 0), ChordStructure::major_triad()),
+            Chord::new(Note::new(G, 0), ChordStructure::major_triad().insert((N7, 0))),
+        ]).unwrap();
+
+        assert_eq!(result, Ok((expected, "")));
+    }
+
+    #[test]
+    fn parse_word_chords() {
+        let result = parser(chord).parse("C major");
+        let expected = Chord::new(Note::new(C, 0), ChordStructure::major_triad());
+        assert_eq!(result, Ok((expected, "")));
+
+        let result = parser(chord).parse("A minor 7");
+        let expected = Chord::new(
+            Note::new(A, 0),
+            ChordStructure::new()
+                .insert_many(&[(N3, -1), (N5, 0), (N7, 0)])
+        );
+        assert_eq!(result, Ok((expected, "")));
+
+        let result = parser(chord).parse("G dominant");
+        let expected = parser(chord).parse("G7");
+        assert_eq!(result, expected);
+
+        let result = parser(chord).parse("Bb diminished 7");
+        let expected = parser(chord).parse("Bbdim7");
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn parse_scientific_pitch() {
+        assert_eq!(parser(scientific_pitch).parse("A4"), Ok(((Note::new(A, 0), 4), "")));
+        assert_eq!(parser(scientific_pitch).parse("Ebb2"), Ok(((Note::new(E, -2), 2), "")));
+        assert_eq!(parser(scientific_pitch).parse("C#-1"), Ok(((Note::new(C, 1), -1), "")));
+        assert!(parser(scientific_pitch).parse("C").is_err());
+    }
+
+    #[test]
+    fn parse_case_insensitive_keywords() {
+        let maj7 = parser(chord).parse("Cmaj7");
+        assert_eq!(parser(chord).parse("CMAJ7"), maj7);
+        assert_eq!(parser(chord).parse("CMaj7"), maj7);
+
+        let expected = Chord::new(Note::new(C, 0), ChordStructure::minor_triad());
+        assert_eq!(parser(chord).parse("Cmin"), Ok((expected.clone(), "")));
+        assert_eq!(parser(chord).parse("CMIN"), Ok((expected, "")));
+
+        assert_eq!(parser(chord).parse("CDim7"), parser(chord).parse("Cdim7"));
+        assert_eq!(parser(chord).parse("C AUGMENTED"), parser(chord).parse("Caug"));
+        assert_eq!(parser(chord).parse("CSUS4"), parser(chord).parse("Csus4"));
+        assert_eq!(parser(chord).parse("CADD9"), parser(chord).parse("Cadd9"));
+    }
+
+    #[test]
+    fn parse_suspensions_and_added_tones() {
+        let parse = |input| parser(chord).parse(input).map(|c| c.0.structure);
+        let structure = |components: &[ChordComponent]| {
+            Ok(ChordStructure::default().insert((N1, 0)).insert_many(components))
+        };
+
+        assert_eq!(parse("Csus4"), structure(&[(N4, 0), (N5, 0)]));
+        assert_eq!(parse("Csus"), structure(&[(N4, 0), (N5, 0)]));
+        assert_eq!(parse("Csus2"), structure(&[(N2, 0), (N5, 0)]));
+        assert_eq!(parse("C7sus4(b9)"), structure(&[(N4, 0), (N5, 0), (N7, 0), (N9, -1)]));
+        assert_eq!(parse("Cadd9"), structure(&[(N3, 0), (N5, 0), (N9, 0)]));
+        assert_eq!(parse("Cmadd9"), structure(&[(N3, -1), (N5, 0), (N9, 0)]));
+        assert_eq!(parse("Csus2add11"), structure(&[(N2, 0), (N5, 0), (N11, 0)]));
+        assert_eq!(parse("Cmaj7"), structure(&[(N3, 0), (N5, 0), (N7, 1)]));
+    }
+
+    #[test]
+    fn parse_lowercase_minor() {
+        let minor = Chord::from_shorthand("Cm").unwrap();
+        let major = Chord::from_shorthand("C").unwrap();
+
+        assert_eq!(parse_chord_lowercase_minor().parse("c"), Ok((minor, "")));
+        assert_eq!(parse_chord_lowercase_minor().parse("C"), Ok((major, "")));
+        assert_eq!(parse_chord_lowercase_minor().parse("bb7/F"),
+                   Ok((Chord::from_shorthand("Bbm7/F").unwrap(), "")));
+        assert!(parse_chord().parse("c").is_err());
+    }
+
+    #[test]
+    fn parse_lowercase_explicit_quality() {
+        let parse = |input| parse_chord_lowercase_minor().parse(input).map(|c| c.0);
+        let shorthand = |symbol| Ok(Chord::from_shorthand(symbol).unwrap());
+
+        assert_eq!(parse("c major"), shorthand("C"));
+        assert_eq!(parse("caug"), shorthand("Caug"));
+        assert_eq!(parse("c dominant 7"), shorthand("C7"));
+        assert_eq!(parse("cmaj7"), shorthand("CMaj7"));
+        assert_eq!(parse("c6"), shorthand("Cm6"));
+        assert_eq!(parse("c7(b9)"), shorthand("Cm7(b9)"));
+    }
+
+    #[test]
+    fn parse_dash_and_triangle_aliases() {
+        let shorthand = |symbol| Chord::from_shorthand(symbol).unwrap();
+
+        assert_eq!(parser(chord).parse("C-7"), Ok((shorthand("Cm7"), "")));
+        assert_eq!(parser(chord).parse("CΔ7"), Ok((shorthand("CMaj7"), "")));
+        assert_eq!(parser(chord).parse("C-Δ7"), Ok((shorthand("CmMaj7"), "")));
+        assert_eq!(parser(chord).parse("C-/Eb"), Ok((shorthand("Cm/Eb"), "")));
+
+        assert!(parse_chord().parse("C7-").is_err());
+        assert!(parse_chord().parse("C--").is_err());
+    }
 }
@@ -0,0 +1,428 @@
+//! Functions operating on sequences of chords.
+
+use chord::*;
+use key::{harmonic_function, roman_numeral, HarmonicFunction};
+use scale::{Mode, Scale};
+
+/// An ordered sequence of chords.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Progression {
+    /// The chords in the order they are played
+    pub chords: Vec<Chord>
+}
+
+impl Progression {
+    /// Construct and return a new `Progression`.
+    pub fn new(chords: Vec<Chord>) -> Progression {
+        Progression { chords }
+    }
+
+    /// Return the progression with its chords in reverse order.
+    pub fn retrograde(&self) -> Progression {
+        Progression::new(self.chords.iter().rev().cloned().collect())
+    }
+
+    /// Returns the tension of each chord as per `Chord::tension`, weighted by
+    /// how long it is held for.
+    ///
+    /// `durations` gives the length of each chord in order, in any unit. Any
+    /// chords beyond the end of `durations` (or durations beyond the end of
+    /// the progression) are ignored.
+    pub fn total_tension(&self, durations: &[u32]) -> u64 {
+        self.chords.iter()
+            .zip(durations)
+            .map(|(chord, &duration)| u64::from(chord.tension()) * u64::from(duration))
+            .sum()
+    }
+}
+
+/// The highest capo position that will be suggested.
+pub const MAX_CAPO_FRET: u8 = 7;
+
+/// Returns whether a chord can be played as a common open chord shape on a
+/// guitar in standard tuning.
+///
+/// This only considers major, minor and seventh chords rooted on `C`, `G`,
+/// `D`, `A` or `E`.
+fn is_open_shape(chord: &Chord) -> bool {
+    use chord::NoteClass::*;
+
+    let root = match chord.root.root {
+        C | G | D | A | E => chord.root.offset == 0,
+        _ => false,
+    };
+
+    let structure = &chord.structure;
+    let third = matches!(structure.get(PitchClass::N3), Some(0) | Some(-1));
+    let seventh = matches!(structure.get(PitchClass::N7), None | Some(0) | Some(1));
+    let extended = [PitchClass::N2, PitchClass::N4, PitchClass::N6, PitchClass::N9,
+                    PitchClass::N11, PitchClass::N13]
+        .iter()
+        .any(|&class| structure.get(class).is_some());
+
+    root && third && seventh && !extended
+        && structure.get(PitchClass::N5) == Some(0)
+        && chord.slash_root.is_none()
+}
+
+/// Find a capo position which allows the progression to be played with as
+/// many open chord shapes as possible.
+///
+/// Returns the capo fret along with the chord shapes that would be fingered
+/// when the capo is placed there. When multiple positions are equally good
+/// the lowest fret is chosen.
+pub fn capo_suggestion(progression: &[Chord]) -> (u8, Vec<Chord>) {
+    let mut best = (0, progression.to_vec());
+    let mut best_score = progression.iter().filter(|c| is_open_shape(c)).count();
+
+    for fret in 1..MAX_CAPO_FRET + 1 {
+        let shapes: Vec<_> =
+            progression.iter().map(|c| c.transpose(-i32::from(fret))).collect();
+        let score = shapes.iter().filter(|c| is_open_shape(c)).count();
+
+        if score > best_score {
+            best = (fret, shapes);
+            best_score = score;
+        }
+    }
+
+    best
+}
+
+/// Returns the inversion of a chord whose bass lies closest to `prev_bass`,
+/// for a smooth bass line.
+///
+/// The candidates are those of `Chord::as_slash_inversions`, and the distance
+/// is the fewest semi-tones between the bass notes in either direction. Root
+/// position is preferred if it is among the closest, and otherwise the
+/// earliest inversion. For example, after a bass of `C` the chord `G` is best
+/// played as `G/B`.
+pub fn best_inversion_for_bassline(chord: &Chord, prev_bass: &Note) -> Chord {
+    let distance = |inversion: &Chord| {
+        let bass = inversion.slash_root.unwrap_or(inversion.root);
+        let up = (bass.pitch_class() + 12 - prev_bass.pitch_class()) % 12;
+        up.min(12 - up)
+    };
+
+    chord.as_slash_inversions()
+        .into_iter()
+        .min_by_key(distance)
+        .unwrap()
+}
+
+/// Returns whether the progression resolves by ending on a chord of tonic
+/// function in the given key, as per `key::harmonic_function`.
+///
+/// An empty progression doesn't resolve.
+pub fn resolves(progression: &[Chord], key: Note) -> bool {
+    progression.last().is_some_and(|last| {
+        harmonic_function(last, key) == Some(HarmonicFunction::Tonic)
+    })
+}
+
+/// Returns the total number of semi-tones the notes of voicing `to` lie from
+/// their nearest note in voicing `from`.
+fn voice_movement(from: &[(Note, i8)], to: &[(Note, i8)]) -> i32 {
+    to.iter()
+        .map(|&(note, octave)| {
+            let pitch = note.absolute_pitch(octave);
+            from.iter()
+                .map(|&(n, o)| (n.absolute_pitch(o) - pitch).abs())
+                .min()
+                .unwrap_or(0)
+        })
+        .sum()
+}
+
+/// Returns a voicing for each chord of a progression which moves as little as
+/// possible from the voicing before it.
+///
+/// The first chord is in closed position with its bass in `base_octave`, as
+/// per `Chord::closed_voicing`. Each following chord is greedily given the
+/// voicing, within an octave either side of the previous one, whose notes lie
+/// the fewest semi-tones in total from their nearest previous note. Of
+/// equally good voicings the one spanning the smallest range is chosen. A
+/// `Dm7 G7 CMaj7` moves by fewer than `6` semi-tones in total.
+///
+/// A chord without any notes is given an empty voicing, and the chord after
+/// it leads on from the last voicing which has notes.
+pub fn voice_progression(progression: &[Chord], base_octave: i8) -> Vec<Vec<(Note, i8)>> {
+    let mut voicings: Vec<Vec<(Note, i8)>> = Vec::new();
+
+    for chord in progression {
+        if chord.iter().next().is_none() {
+            voicings.push(Vec::new());
+            continue;
+        }
+
+        let previous = voicings.iter().rev().find(|voicing| !voicing.is_empty());
+
+        let voicing = match previous {
+            None => chord.closed_voicing(base_octave),
+            Some(previous) => {
+                let (low, low_octave) = previous[0];
+                let (high, high_octave) = previous[previous.len() - 1];
+                let span = |voicing: &Vec<(Note, i8)>| {
+                    let (first, last) = (voicing[0], voicing[voicing.len() - 1]);
+                    last.0.absolute_pitch(last.1) - first.0.absolute_pitch(first.1)
+                };
+
+                // Every note can be placed within the octave either side, so
+                // there is always at least one voicing.
+                chord.voicings_in_range((low, low_octave - 1), (high, high_octave + 1))
+                    .into_iter()
+                    .min_by_key(|voicing| (voice_movement(previous, voicing), span(voicing)))
+                    .unwrap()
+            }
+        };
+
+        voicings.push(voicing);
+    }
+
+    voicings
+}
+
+/// Suggest chords which are likely to follow the progression in the given
+/// major key, most likely first.
+///
+/// The suggestions follow simple functional rules based on the final chord.
+/// A chord with a dominant function (see `Chord::is_dominant_functioning`)
+/// resolves to the chord a fifth below, with the submediant offered as a
+/// deceptive resolution of the dominant. Other diatonic chords move as:
+///
+/// * `I` to `IV`, `V`, `vi` or `ii`
+/// * `ii` to `V` or `vii°`
+/// * `iii` to `vi` or `IV`
+/// * `IV` to `V`, `I` or `ii`
+/// * `V` to `I` or `vi`
+/// * `vi` to `ii` or `IV`
+/// * `vii°` to `I` or `iii`
+///
+/// An empty progression begins on the tonic, while a chord rooted outside of
+/// the key returns to `I` or `V`.
+pub fn suggest_next(progression: &[Chord], key: Note) -> Vec<Chord> {
+    const NEXT: [&[usize]; 7] = [
+        &[3, 4, 5, 1], &[4, 6], &[5, 3], &[4, 0, 1], &[0, 5], &[1, 3], &[0, 2],
+    ];
+
+    let triads = Scale::new(key, Mode::Ionian).harmonize(ChordExtent::Triad);
+    let degree_of = |note: &Note| {
+        triads.iter().position(|triad| triad.root.pitch_class() == note.pitch_class())
+    };
+
+    let last = match progression.last() {
+        Some(chord) => chord,
+        None => return vec![triads[0].clone()],
+    };
+
+    let degrees: &[usize] = if last.is_dominant_functioning() {
+        let target = last.root.get_relative((PitchClass::N4, 0));
+
+        match degree_of(&target) {
+            Some(0) => &[0, 5],
+            Some(degree) => return vec![triads[degree].clone()],
+            None => return vec![Chord::new(target, ChordStructure::major_triad())],
+        }
+    } else {
+        match degree_of(&last.root) {
+            Some(degree) => NEXT[degree],
+            None => &[0, 4],
+        }
+    };
+
+    degrees.iter().map(|&degree| triads[degree].clone()).collect()
+}
+
+/// Returns the index of the first chord of each `ii-V-I` within the
+/// progression in the given key.
+///
+/// Both the major form, such as `Dm7 G7 Cmaj7` in `C`, and the minor form
+/// `iiø-V7-i`, such as `Dm7(b5) G7 Cm`, are found. The chords are matched by
+/// their roots and thirds, so triads and extended chords count as well, but
+/// the `V` must not have a major seventh. Slash roots are ignored.
+pub fn find_ii_v_i(progression: &[Chord], key: Note) -> Vec<usize> {
+    let is_on = |chord: &Chord, semitones: u8| {
+        (chord.root.pitch_class() + 12 - key.pitch_class()) % 12 == semitones
+    };
+
+    let third = |chord: &Chord| chord.structure.get(PitchClass::N3);
+    let fifth = |chord: &Chord| chord.structure.get(PitchClass::N5);
+
+    progression.windows(3)
+        .enumerate()
+        .filter(|&(_, window)| {
+            let (two, five, one) = (&window[0], &window[1], &window[2]);
+
+            let dominant = third(five) == Some(0)
+                && five.structure.get(PitchClass::N7) != Some(1);
+            let major = fifth(two) != Some(-1) && third(one) == Some(0);
+            let minor = fifth(two) == Some(-1) && third(one) == Some(-1);
+
+            is_on(two, 2) && is_on(five, 7) && is_on(one, 0)
+                && third(two) == Some(-1) && dominant && (major || minor)
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Returns the progression in the given key as a Markdown table.
+///
+/// Each chord is given a row with its symbol, its roman numeral as per
+/// `key::roman_numeral` and its notes in the order given by `Chord::iter`.
+pub fn progression_to_markdown(progression: &[Chord], key: Note) -> String {
+    let mut table = String::from("| Chord | Numeral | Notes |\n|---|---|---|\n");
+
+    for chord in progression {
+        let notes: Vec<_> = chord.iter().map(|note| note.to_string()).collect();
+
+        table.push_str(&format!("| {} | {} | {} |\n",
+                                chord, roman_numeral(chord, key), notes.join(" ")));
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    fn chords(symbols: &[&str]) -> Vec<Chord> {
+        symbols.iter().map(|s| Chord::from_shorthand(s).unwrap()).collect()
+    }
+
+    #[test]
+    fn retrograde() {
+        let progression = Progression::new(chords(&["C", "Am", "F", "G7"]));
+        let expected = Progression::new(chords(&["G7", "F", "Am", "C"]));
+
+        assert_eq!(progression.retrograde(), expected);
+        assert_eq!(progression.retrograde().retrograde(), progression);
+    }
+
+    #[test]
+    fn total_tension() {
+        let progression = Progression::new(chords(&["C", "F", "G7", "C"]));
+
+        assert_eq!(progression.total_tension(&[4, 4, 2, 6]), 6);
+        assert_eq!(progression.total_tension(&[4, 4]), 0);
+    }
+
+    #[test]
+    fn capo_for_flat_key() {
+        let progression = chords(&["Eb", "Ab", "Bb7"]);
+        let (fret, shapes) = capo_suggestion(&progression);
+
+        assert_eq!(fret, 1);
+        assert_eq!(shapes, chords(&["D", "G", "A7"]));
+    }
+
+    #[test]
+    fn capo_for_open_key() {
+        let progression = chords(&["G", "Em", "C", "D"]);
+        assert_eq!(capo_suggestion(&progression), (0, progression));
+    }
+
+    #[test]
+    fn suggest_next_resolves_dominants() {
+        use chord::NoteClass::*;
+
+        let key = Note::new(C, 0);
+
+        let suggestions = suggest_next(&chords(&["Dm7", "G7"]), key);
+        assert_eq!(suggestions, chords(&["C", "Am"]));
+
+        let suggestions = suggest_next(&chords(&["C", "E7"]), key);
+        assert_eq!(suggestions, chords(&["Am"]));
+
+        assert_eq!(suggest_next(&chords(&["Dm"]), key)[0], chords(&["G"])[0]);
+        assert_eq!(suggest_next(&[], key), chords(&["C"]));
+    }
+
+    #[test]
+    fn find_ii_v_i_patterns() {
+        use chord::NoteClass::*;
+
+        let key = Note::new(C, 0);
+
+        assert_eq!(find_ii_v_i(&chords(&["Dm7", "G7", "Cmaj7"]), key), vec![0]);
+        assert_eq!(find_ii_v_i(&chords(&["C", "Dm7(b5)", "G7", "Cm", "Dm", "G", "C"]), key),
+                   vec![1, 4]);
+        assert!(find_ii_v_i(&chords(&["Dm7(b5)", "G7", "C"]), key).is_empty());
+        assert!(find_ii_v_i(&chords(&["Dm7", "G7", "Cmaj7"]), Note::new(F, 0)).is_empty());
+    }
+
+    #[test]
+    fn markdown_table() {
+        use chord::NoteClass::*;
+
+        let table = progression_to_markdown(&chords(&["Dm7", "G7", "C"]), Note::new(C, 0));
+        let lines: Vec<_> = table.lines().collect();
+
+        assert_eq!(lines[0], "| Chord | Numeral | Notes |");
+        assert_eq!(lines[1], "|---|---|---|");
+        assert_eq!(lines[2], "| Dm7 | ii7 | D F A C |");
+        assert_eq!(lines.len(), 5);
+    }
+
+    #[test]
+    fn smooth_bassline() {
+        use chord::NoteClass::*;
+
+        let shorthand = |symbol| Chord::from_shorthand(symbol).unwrap();
+
+        let g = best_inversion_for_bassline(&shorthand("G"), &Note::new(C, 0));
+        assert_eq!(g, shorthand("G/B"));
+
+        let am = best_inversion_for_bassline(&shorthand("Am"), &Note::new(B, 0));
+        assert_eq!(am, shorthand("Am/C"));
+
+        let c = best_inversion_for_bassline(&shorthand("C/E"), &Note::new(D, 0));
+        assert_eq!(c, shorthand("C"));
+    }
+
+    #[test]
+    fn progression_resolves() {
+        use chord::NoteClass::*;
+
+        let key = Note::new(C, 0);
+
+        assert!(resolves(&chords(&["F", "G7", "C"]), key));
+        assert!(resolves(&chords(&["Dm7", "G7", "CMaj7"]), key));
+        assert!(!resolves(&chords(&["C", "F", "G"]), key));
+        assert!(resolves(&chords(&["G7", "Am"]), key));
+        assert!(resolves(&chords(&["Am", "G", "Em"]), key));
+        assert!(!resolves(&chords(&["C", "G7"]), key));
+        assert!(!resolves(&chords(&["C", "Eb"]), key));
+        assert!(!resolves(&[], key));
+    }
+
+    #[test]
+    fn voice_leading_ii_v_i() {
+        let voicings = voice_progression(&chords(&["Dm7", "G7", "CMaj7"]), 4);
+
+        let movement: i32 = voicings.windows(2)
+            .map(|pair| voice_movement(&pair[0], &pair[1]))
+            .sum();
+
+        assert_eq!(voicings.len(), 3);
+        assert_eq!(voicings[0], chords(&["Dm7"])[0].closed_voicing(4));
+        assert!(movement < 6);
+    }
+
+    #[test]
+    fn voice_leading_over_empty_chord() {
+        use chord::NoteClass::*;
+
+        let empty = Chord::new(Note::new(C, 0), ChordStructure::default());
+        let progression = vec![empty.clone(), chords(&["Dm7"])[0].clone(), empty,
+                               chords(&["G7"])[0].clone()];
+
+        let voicings = voice_progression(&progression, 4);
+        let expected = voice_progression(&chords(&["Dm7", "G7"]), 4);
+
+        assert!(voicings[0].is_empty() && voicings[2].is_empty());
+        assert_eq!(voicings[1], expected[0]);
+        assert_eq!(voicings[3], expected[1]);
+    }
+}
@@ -0,0 +1,202 @@
+//! Types representing scales and functions relating them to chords.
+
+use chord::*;
+
+/// One of the seven modes of the major scale.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Mode {
+    Ionian, Dorian, Phrygian, Lydian, Mixolydian, Aeolian, Locrian
+}
+
+impl Mode {
+    /// Returns the degrees of this mode as intervals above its root.
+    ///
+    /// As in chords, an unaltered seventh is a minor seventh, so the major
+    /// seventh of `Ionian` is a sharpened `PitchClass::N7`.
+    pub fn degrees(&self) -> [ChordComponent; 7] {
+        use self::Mode::*;
+        use chord::PitchClass::*;
+
+        let (second, third, fourth, fifth, sixth, seventh) = match *self {
+            Ionian     => (0, 0, 0, 0, 0, 1),
+            Dorian     => (0, -1, 0, 0, 0, 0),
+            Phrygian   => (-1, -1, 0, 0, -1, 0),
+            Lydian     => (0, 0, 1, 0, 0, 1),
+            Mixolydian => (0, 0, 0, 0, 0, 0),
+            Aeolian    => (0, -1, 0, 0, -1, 0),
+            Locrian    => (-1, -1, 0, -1, -1, 0),
+        };
+
+        [
+            (N1, 0), (N2, second), (N3, third), (N4, fourth),
+            (N5, fifth), (N6, sixth), (N7, seventh),
+        ]
+    }
+}
+
+/// A heptatonic scale formed by a mode built upon a root note.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Scale {
+    /// The first degree of the scale
+    pub root: Note,
+
+    /// The pattern of intervals above the root
+    pub mode: Mode,
+}
+
+impl Scale {
+    /// Construct and return a new `Scale`.
+    pub fn new(root: Note, mode: Mode) -> Scale {
+        Scale { root, mode }
+    }
+
+    /// Returns the notes of this scale in ascending order from the root.
+    ///
+    /// Each note is spelled with a different `NoteClass`, so `F` major has a
+    /// `Bb` rather than an `A#`.
+    pub fn notes(&self) -> Vec<Note> {
+        self.mode.degrees()
+            .iter()
+            .map(|&degree| self.root.get_relative(degree))
+            .collect()
+    }
+
+    /// Return this scale with its root moved by the given number of
+    /// semi-tones, keeping its mode.
+    ///
+    /// The root is transposed as per `Note::transpose`, and the other notes
+    /// are spelled from it as usual, so `C` major up `7` is `G` major.
+    pub fn transpose(&self, semitones: i32) -> Scale {
+        Scale::new(self.root.transpose(semitones), self.mode)
+    }
+
+    /// Returns the chord built upon each degree of this scale using only the
+    /// notes of the scale, starting from the root.
+    ///
+    /// Each chord is formed by stacking thirds from the scale up to `extent`,
+    /// so the triads of `C` major are `C`, `Dm`, `Em`, `F`, `G`, `Am` and
+    /// `Bdim`.
+    pub fn harmonize(&self, extent: ChordExtent) -> Vec<Chord> {
+        let notes = self.notes();
+        let steps: &[usize] = match extent {
+            ChordExtent::Triad      => &[0, 2, 4],
+            ChordExtent::Seventh    => &[0, 2, 4, 6],
+            ChordExtent::Ninth      => &[0, 2, 4, 6, 8],
+            ChordExtent::Eleventh   => &[0, 2, 4, 6, 8, 10],
+            ChordExtent::Thirteenth => &[0, 2, 4, 6, 8, 10, 12],
+        };
+
+        (0..notes.len())
+            .map(|degree| {
+                let root = notes[degree];
+                let tones: Vec<_> = steps.iter()
+                    .map(|step| notes[(degree + step) % notes.len()])
+                    .collect();
+
+                // Each tone is spelled with a different letter, so no two
+                // compete for the same interval.
+                Chord::new(root, ChordStructure::from_notes(&root, &tones).unwrap())
+            })
+            .collect()
+    }
+}
+
+/// Returns the default scale for improvising over a chord.
+///
+/// The scale is built on the root of the chord and chosen by its quality:
+///
+/// * Major chords use `Ionian`, or `Lydian` if there is a sharpened eleventh
+/// * Dominant chords use `Mixolydian`
+/// * Minor chords use `Dorian`, or `Aeolian` if there is a flattened sixth
+///   or thirteenth
+/// * Half-diminished and diminished chords use `Locrian`
+/// * Augmented chords use `Lydian`
+///
+/// No mode fits a diminished or augmented chord exactly (each differs in its
+/// seventh or fifth respectively), so these are the nearest matches. Chords
+/// without a quality use `Ionian`. Any slash root is ignored.
+pub fn recommended_scale(chord: &Chord) -> Scale {
+    use self::Mode::*;
+
+    let structure = &chord.structure;
+    let has = |component: ChordComponent| structure.get(component.0) == Some(component.1);
+
+    let mode = match structure.quality() {
+        Some(ChordQuality::Major) if has((PitchClass::N11, 1)) => Lydian,
+        Some(ChordQuality::Major) => Ionian,
+        Some(ChordQuality::Dominant) => Mixolydian,
+        Some(ChordQuality::Minor)
+            if has((PitchClass::N6, -1)) || has((PitchClass::N13, -1)) => Aeolian,
+        Some(ChordQuality::Minor) => Dorian,
+        Some(ChordQuality::HalfDiminished) | Some(ChordQuality::Diminished) => Locrian,
+        Some(ChordQuality::Augmented) => Lydian,
+        None => Ionian,
+    };
+
+    Scale::new(chord.root, mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chord::NoteClass::*;
+
+    fn recommended(symbol: &str) -> Scale {
+        recommended_scale(&Chord::from_shorthand(symbol).unwrap())
+    }
+
+    #[test]
+    fn scale_notes() {
+        let notes = vec![
+            Note::new(F, 0), Note::new(G, 0), Note::new(A, 0), Note::new(B, -1),
+            Note::new(C, 0), Note::new(D, 0), Note::new(E, 0),
+        ];
+        assert_eq!(Scale::new(Note::new(F, 0), Mode::Ionian).notes(), notes);
+
+        let notes = vec![
+            Note::new(D, 0), Note::new(E, 0), Note::new(F, 0), Note::new(G, 0),
+            Note::new(A, 0), Note::new(B, 0), Note::new(C, 0),
+        ];
+        assert_eq!(Scale::new(Note::new(D, 0), Mode::Dorian).notes(), notes);
+    }
+
+    #[test]
+    fn recommended_scale_common_qualities() {
+        assert_eq!(recommended("CMaj7"), Scale::new(Note::new(C, 0), Mode::Ionian));
+        assert_eq!(recommended("CMaj7(#11)"), Scale::new(Note::new(C, 0), Mode::Lydian));
+        assert_eq!(recommended("G7"), Scale::new(Note::new(G, 0), Mode::Mixolydian));
+        assert_eq!(recommended("Dm7"), Scale::new(Note::new(D, 0), Mode::Dorian));
+        assert_eq!(recommended("Bm7(b5)"), Scale::new(Note::new(B, 0), Mode::Locrian));
+    }
+
+    #[test]
+    fn scale_harmonize() {
+        let scale = Scale::new(Note::new(C, 0), Mode::Ionian);
+
+        let triads: Vec<_> = ["C", "Dm", "Em", "F", "G", "Am", "Bdim"].iter()
+            .map(|symbol| Chord::from_shorthand(symbol).unwrap())
+            .collect();
+        assert_eq!(scale.harmonize(ChordExtent::Triad), triads);
+
+        let sevenths: Vec<_> = scale.harmonize(ChordExtent::Seventh).iter()
+            .map(Chord::to_string)
+            .collect();
+        assert_eq!(sevenths, ["CMaj7", "Dm7", "Em7", "FMaj7", "G7", "Am7", "Bm7(b5)"]);
+
+        let dorian = Scale::new(Note::new(D, 0), Mode::Dorian);
+        let elevenths = dorian.harmonize(ChordExtent::Eleventh);
+        assert_eq!(elevenths[0], Chord::from_shorthand("Dm11").unwrap());
+    }
+
+    #[test]
+    fn scale_transpose() {
+        let g_major = Scale::new(Note::new(C, 0), Mode::Ionian).transpose(7);
+
+        assert_eq!(g_major, Scale::new(Note::new(G, 0), Mode::Ionian));
+        assert_eq!(g_major.notes()[6], Note::new(F, 1));
+
+        let dorian = Scale::new(Note::new(D, 0), Mode::Dorian).transpose(-2);
+        assert_eq!(dorian, Scale::new(Note::new(C, 0), Mode::Dorian));
+    }
+}